@@ -15,3 +15,18 @@ pub trait Can {
     /// Blocks until a frame was received or an error occurred.
     fn receive(&mut self) -> Result<Self::Frame, Self::Error>;
 }
+
+impl<T: Can + ?Sized> Can for &mut T {
+    type Frame = T::Frame;
+    type Error = T::Error;
+
+    #[inline]
+    fn transmit(&mut self, frame: &Self::Frame) -> Result<(), Self::Error> {
+        T::transmit(self, frame)
+    }
+
+    #[inline]
+    fn receive(&mut self) -> Result<Self::Frame, Self::Error> {
+        T::receive(self)
+    }
+}
@@ -93,9 +93,50 @@ impl ExtendedId {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for StandardId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StandardId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = u16::deserialize(deserializer)?;
+        Self::new(raw).ok_or_else(|| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(raw.into()),
+                &"a standard CAN ID (0..=0x7FF)",
+            )
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ExtendedId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ExtendedId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = u32::deserialize(deserializer)?;
+        Self::new(raw).ok_or_else(|| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(raw.into()),
+                &"an extended CAN ID (0..=0x1FFF_FFFF)",
+            )
+        })
+    }
+}
+
 /// A CAN Identifier (standard or extended).
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Id {
     /// Standard 11-bit Identifier (`0..=0x7FF`).
     Standard(StandardId),
@@ -216,4 +257,18 @@ mod tests {
         assert!(Id::Extended(ExtendedId((1 << 11) - 1)) < Id::Standard(StandardId(1)));
         assert!(Id::Standard(StandardId(1)) < Id::Extended(ExtendedId::MAX));
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_id_out_of_range() {
+        use serde::de::{value::Error, Deserialize, IntoDeserializer};
+
+        let standard = |raw: u16| StandardId::deserialize(raw.into_deserializer());
+        assert_eq!(standard(0x7FF), Ok::<_, Error>(StandardId::MAX));
+        assert!(standard(0x800).is_err());
+
+        let extended = |raw: u32| ExtendedId::deserialize(raw.into_deserializer());
+        assert_eq!(extended(0x1FFF_FFFF), Ok::<_, Error>(ExtendedId::MAX));
+        assert!(extended(0x2000_0000).is_err());
+    }
 }
@@ -26,3 +26,18 @@ pub trait Can {
     /// Returns a received frame if available.
     fn receive(&mut self) -> nb::Result<Self::Frame, Self::Error>;
 }
+
+impl<T: Can + ?Sized> Can for &mut T {
+    type Frame = T::Frame;
+    type Error = T::Error;
+
+    #[inline]
+    fn transmit(&mut self, frame: &Self::Frame) -> nb::Result<Option<Self::Frame>, Self::Error> {
+        T::transmit(self, frame)
+    }
+
+    #[inline]
+    fn receive(&mut self) -> nb::Result<Self::Frame, Self::Error> {
+        T::receive(self)
+    }
+}
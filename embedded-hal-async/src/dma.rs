@@ -0,0 +1,37 @@
+//! DMA transfer handles.
+//!
+//! See [the docs on embedded-hal](embedded_hal::dma) for the ownership-transfer pattern.
+
+pub use embedded_hal::dma::{Error, ErrorKind, ErrorType, Transfer};
+
+/// A DMA channel running a background transfer, with async completion.
+pub trait Channel: embedded_hal::dma::Channel {
+    /// Wait until the transfer has finished and the hardware no longer accesses the buffer.
+    ///
+    /// If the transfer has already finished, return immediately.
+    async fn wait_for_completion(&mut self) -> Result<(), Self::Error>;
+}
+
+impl<T: Channel + ?Sized> Channel for &mut T {
+    #[inline]
+    async fn wait_for_completion(&mut self) -> Result<(), Self::Error> {
+        T::wait_for_completion(self).await
+    }
+}
+
+/// Wait until `transfer` has finished, and give the buffer and the channel back.
+///
+/// This is the async equivalent of [`Transfer::wait`]. The buffer and the channel are also
+/// given back on error. If the returned future is dropped before completion, the transfer
+/// is dropped too, which aborts it.
+#[allow(clippy::type_complexity)]
+pub async fn wait<BUF, CHANNEL: Channel>(
+    mut transfer: Transfer<BUF, CHANNEL>,
+) -> Result<(BUF, CHANNEL), (CHANNEL::Error, BUF, CHANNEL)> {
+    match transfer.channel_mut().wait_for_completion().await {
+        Ok(()) => transfer.wait(),
+        Err(e) => match transfer.abort() {
+            Ok((buf, channel)) | Err((_, buf, channel)) => Err((e, buf, channel)),
+        },
+    }
+}
@@ -17,8 +17,8 @@
 //! `SevenBitAddress` has been set as default mode and thus can be omitted if desired.
 
 pub use embedded_hal::i2c::{
-    AddressMode, Error, ErrorKind, ErrorType, NoAcknowledgeSource, Operation, SevenBitAddress,
-    TenBitAddress,
+    AddressMode, Capabilities, Error, ErrorKind, ErrorType, NoAcknowledgeSource, Operation,
+    SevenBitAddress, TenBitAddress,
 };
 
 /// Async I2c.
@@ -5,5 +5,6 @@
 
 pub mod delay;
 pub mod digital;
+pub mod dma;
 pub mod i2c;
 pub mod spi;
@@ -1,7 +1,8 @@
 //! SPI master mode traits.
 
 pub use embedded_hal::spi::{
-    Error, ErrorKind, ErrorType, Mode, Operation, Phase, Polarity, MODE_0, MODE_1, MODE_2, MODE_3,
+    Capabilities, Error, ErrorKind, ErrorType, Mode, Operation, Phase, Polarity, MODE_0, MODE_1,
+    MODE_2, MODE_3,
 };
 
 /// SPI device trait.
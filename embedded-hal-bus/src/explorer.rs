@@ -0,0 +1,114 @@
+//! Bus exploration helpers for host-side bring-up tools.
+//!
+//! These functions work over any [`I2c`] or [`SpiDevice`] implementation, so tools such as
+//! `i2cdetect`/`i2cdump`-like utilities can be built directly on the `embedded-hal` traits,
+//! e.g. on top of a Linux or USB-bridge implementation.
+//!
+//! ```
+//! use embedded_hal_bus::explorer;
+//! # use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+//! # pub struct I2c0;
+//! # impl ErrorType for I2c0 { type Error = ErrorKind; }
+//! # impl I2c for I2c0 {
+//! #     fn transaction(&mut self, address: u8, ops: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+//! #         if address != 0x48 {
+//! #             return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+//! #         }
+//! #         for op in ops {
+//! #             if let Operation::Read(buf) = op { buf.fill(0xAB); }
+//! #         }
+//! #         Ok(())
+//! #     }
+//! # }
+//! # let mut i2c = I2c0;
+//!
+//! for address in explorer::scan_i2c(&mut i2c)? {
+//!     let registers = explorer::dump_i2c_registers(&mut i2c, address, 0x00, 32)?;
+//!     println!("Device at {:#04x}:", address);
+//!     print!("{}", explorer::format_hex_dump(0x00, &registers));
+//! }
+//! # Ok::<(), ErrorKind>(())
+//! ```
+
+use core::fmt::Write;
+use core::ops::RangeInclusive;
+use embedded_hal::i2c::{Error, ErrorKind, I2c};
+use embedded_hal::spi::{Operation, SpiDevice};
+
+/// 7-bit addresses probed by [`scan_i2c`].
+///
+/// Addresses `0x00..=0x07` and `0x78..=0x7F` are reserved by the I2C specification.
+pub const I2C_SCAN_RANGE: RangeInclusive<u8> = 0x08..=0x77;
+
+/// Returns the addresses in [`I2C_SCAN_RANGE`] at which a device acknowledges.
+///
+/// Each address is probed by reading a single byte, which is safe for nearly all devices, unlike
+/// writes which could change a register pointer or trigger an action. Devices with side effects
+/// on read (e.g. clearing interrupt flags) may still be affected.
+///
+/// Addresses that aren't acknowledged are skipped. Any other error aborts the scan.
+pub fn scan_i2c<I2C: I2c>(i2c: &mut I2C) -> Result<Vec<u8>, I2C::Error> {
+    let mut found = Vec::new();
+    for address in I2C_SCAN_RANGE {
+        match i2c.read(address, &mut [0]) {
+            Ok(()) => found.push(address),
+            Err(e) if matches!(e.kind(), ErrorKind::NoAcknowledge(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(found)
+}
+
+/// Read `len` consecutive registers of the I2C device at `address`, starting at `start`.
+///
+/// This writes the 8-bit register address `start`, then reads `len` bytes in the same
+/// transaction, relying on the device auto-incrementing the register address.
+pub fn dump_i2c_registers<I2C: I2c>(
+    i2c: &mut I2C,
+    address: u8,
+    start: u8,
+    len: usize,
+) -> Result<Vec<u8>, I2C::Error> {
+    let mut registers = vec![0; len];
+    i2c.write_read(address, &[start], &mut registers)?;
+    Ok(registers)
+}
+
+/// Send `command` to the SPI device, then read `len` bytes in the same transaction.
+///
+/// SPI register read commands are device-specific (e.g. setting the most significant bit of the
+/// register address), so the caller builds `command`.
+pub fn dump_spi_registers<SPI: SpiDevice>(
+    spi: &mut SPI,
+    command: &[u8],
+    len: usize,
+) -> Result<Vec<u8>, SPI::Error> {
+    let mut registers = vec![0; len];
+    spi.transaction(&mut [Operation::Write(command), Operation::Read(&mut registers)])?;
+    Ok(registers)
+}
+
+/// Format `data` as a hex dump with 16 bytes per line, each line prefixed with its address.
+///
+/// `start` is the address of the first byte of `data`, e.g. the first register dumped.
+///
+/// ```
+/// use embedded_hal_bus::explorer::format_hex_dump;
+///
+/// assert_eq!(
+///     format_hex_dump(0x10, &[0xDE, 0xAD, 0xBE, 0xEF]),
+///     "10: de ad be ef\n",
+/// );
+/// ```
+pub fn format_hex_dump(start: usize, data: &[u8]) -> String {
+    let mut out = String::new();
+    for (i, line) in data.chunks(16).enumerate() {
+        // Writing to a `String` can't fail.
+        let _ = write!(out, "{:02x}:", start + i * 16);
+        for byte in line {
+            let _ = write!(out, " {:02x}", byte);
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,313 @@
+use core::fmt::{self, Debug, Display, Formatter};
+use embedded_hal::i2c::{AddressMode, Capabilities, Error, ErrorKind, ErrorType, I2c, Operation};
+
+use crate::util::OpList;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+#[cfg(feature = "defmt-03")]
+use crate::defmt;
+
+/// Error type for [`ChunkedDevice`] operations.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum ChunkedError<BUS> {
+    /// An operation on the wrapped bus failed.
+    Bus(BUS),
+    /// Splitting the transaction needs more operations than the [`ChunkedDevice`] can hold.
+    TooManyOperations,
+}
+
+impl<BUS: Display> Display for ChunkedError<BUS> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Bus(bus) => write!(f, "I2C bus error: {}", bus),
+            Self::TooManyOperations => write!(f, "Too many operations after splitting"),
+        }
+    }
+}
+
+impl<BUS: Debug + Display> core::error::Error for ChunkedError<BUS> {}
+
+impl<BUS: Error> Error for ChunkedError<BUS> {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Bus(e) => e.kind(),
+            Self::TooManyOperations => ErrorKind::Other,
+        }
+    }
+}
+
+/// [`I2c`] wrapper splitting reads and writes longer than the hardware supports.
+///
+/// Some I2C peripherals can only transfer a limited number of bytes at once, often 255.
+/// `ChunkedDevice` splits every longer [`Operation`] into several operations of the same type.
+/// Adjacent operations of the same type are sent without a repeated start, so the target sees
+/// exactly the same bus traffic as for the original transaction.
+///
+/// The split transaction is built in an [`OpList`] of capacity `N`. Transactions needing more
+/// operations than that fail with [`ChunkedError::TooManyOperations`] without accessing the bus.
+/// Transactions that don't need splitting are passed through as-is.
+///
+/// Through [`Capabilities`], `ChunkedDevice` reports a maximum transfer length of `N` times the
+/// maximum length of the wrapped bus: the longest operation that can be split. Several long
+/// operations in one transaction share the `N` operations, so they must be shorter than that.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_hal::i2c::I2c;
+/// use embedded_hal_bus::i2c::ChunkedDevice;
+/// # use embedded_hal::i2c::{Capabilities, ErrorKind, ErrorType, Operation};
+/// # pub struct I2c0;
+/// # impl ErrorType for I2c0 { type Error = ErrorKind; }
+/// # impl I2c for I2c0 {
+/// #     fn transaction(&mut self, _: u8, ops: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+/// #         assert_eq!(ops.len(), 3);
+/// #         Ok(())
+/// #     }
+/// # }
+/// # impl Capabilities for I2c0 {
+/// #     fn max_transfer_len(&self) -> Option<usize> { Some(255) }
+/// # }
+/// # let i2c = I2c0;
+///
+/// // `i2c` reports a maximum transfer length through `Capabilities`.
+/// let mut i2c = ChunkedDevice::<_>::new(i2c);
+///
+/// // Sent as one 1-byte write and two reads of 255 and 145 bytes.
+/// let mut frame = [0; 400];
+/// i2c.write_read(0x3C, &[0x00], &mut frame).unwrap();
+/// ```
+pub struct ChunkedDevice<BUS, const N: usize = 8> {
+    bus: BUS,
+    max_len: Option<usize>,
+}
+
+impl<BUS, const N: usize> ChunkedDevice<BUS, N> {
+    /// Create a new [`ChunkedDevice`], using the maximum transfer length reported by `bus`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bus` reports a maximum transfer length of 0.
+    #[inline]
+    pub fn new(bus: BUS) -> Self
+    where
+        BUS: Capabilities,
+    {
+        let max_len = bus.max_transfer_len();
+        Self::with_max_transfer_len(bus, max_len)
+    }
+
+    /// Create a new [`ChunkedDevice`] with an explicit maximum transfer length, in bytes.
+    ///
+    /// `None` disables chunking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is `Some(0)`.
+    #[inline]
+    pub fn with_max_transfer_len(bus: BUS, max_len: Option<usize>) -> Self {
+        assert!(max_len != Some(0), "maximum transfer length must not be 0");
+        Self { bus, max_len }
+    }
+
+    /// Destroy the [`ChunkedDevice`], returning the wrapped bus.
+    #[inline]
+    pub fn into_inner(self) -> BUS {
+        self.bus
+    }
+}
+
+impl<BUS: ErrorType, const N: usize> ErrorType for ChunkedDevice<BUS, N> {
+    type Error = ChunkedError<BUS::Error>;
+}
+
+impl<BUS, const N: usize> ChunkedDevice<BUS, N> {
+    /// Returns the length to split operations into, or `None` if `operations` can be sent as-is.
+    #[inline]
+    fn chunk_len(&self, operations: &[Operation<'_>]) -> Option<usize> {
+        self.max_len
+            .filter(|&max_len| operations.iter().any(|op| op_len(op) > max_len))
+    }
+}
+
+impl<A: AddressMode, BUS: I2c<A>, const N: usize> I2c<A> for ChunkedDevice<BUS, N> {
+    fn transaction(
+        &mut self,
+        address: A,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        match self.chunk_len(operations) {
+            Some(max_len) => {
+                let mut chunked = split::<N, _>(operations, max_len)?;
+                self.bus.transaction(address, &mut chunked)
+            }
+            None => self.bus.transaction(address, operations),
+        }
+        .map_err(ChunkedError::Bus)
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+impl<A: AddressMode, BUS: AsyncI2c<A>, const N: usize> AsyncI2c<A> for ChunkedDevice<BUS, N> {
+    async fn transaction(
+        &mut self,
+        address: A,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        match self.chunk_len(operations) {
+            Some(max_len) => {
+                let mut chunked = split::<N, _>(operations, max_len)?;
+                self.bus.transaction(address, &mut chunked).await
+            }
+            None => self.bus.transaction(address, operations).await,
+        }
+        .map_err(ChunkedError::Bus)
+    }
+}
+
+impl<BUS: Capabilities, const N: usize> Capabilities for ChunkedDevice<BUS, N> {
+    #[inline]
+    fn max_transfer_len(&self) -> Option<usize> {
+        self.max_len.map(|max_len| max_len.saturating_mul(N))
+    }
+
+    #[inline]
+    fn max_frequency_hz(&self) -> Option<u32> {
+        self.bus.max_frequency_hz()
+    }
+
+    #[inline]
+    fn supports_ten_bit_addresses(&self) -> bool {
+        self.bus.supports_ten_bit_addresses()
+    }
+
+    #[inline]
+    fn has_dma(&self) -> bool {
+        self.bus.has_dma()
+    }
+}
+
+/// Split every operation longer than `max_len` into several operations of the same type.
+fn split<'a, const N: usize, E>(
+    operations: &'a mut [Operation<'_>],
+    max_len: usize,
+) -> Result<OpList<Operation<'a>, N>, ChunkedError<E>> {
+    let mut chunked = OpList::new();
+    for op in operations.iter_mut() {
+        // Empty operations are kept: they still make the bus send the address.
+        let res = match op {
+            Operation::Read(buf) => {
+                if buf.is_empty() {
+                    chunked.push(Operation::Read(buf))
+                } else {
+                    buf.chunks_mut(max_len)
+                        .try_for_each(|chunk| chunked.push(Operation::Read(chunk)))
+                }
+            }
+            Operation::Write(buf) => {
+                if buf.is_empty() {
+                    chunked.push(Operation::Write(buf))
+                } else {
+                    buf.chunks(max_len)
+                        .try_for_each(|chunk| chunked.push(Operation::Write(chunk)))
+                }
+            }
+        };
+        res.map_err(|_| ChunkedError::TooManyOperations)?;
+    }
+    Ok(chunked)
+}
+
+fn op_len(op: &Operation<'_>) -> usize {
+    match op {
+        Operation::Read(buf) => buf.len(),
+        Operation::Write(buf) => buf.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lens(ops: &[Operation<'_>]) -> ([(bool, usize); 8], usize) {
+        let mut lens = [(false, 0); 8];
+        for (l, op) in lens.iter_mut().zip(ops) {
+            *l = (matches!(op, Operation::Read(_)), op_len(op));
+        }
+        (lens, ops.len())
+    }
+
+    #[test]
+    fn split_exact_multiple() {
+        let mut ops = [Operation::Write(&[0; 6])];
+        let chunked = split::<4, ()>(&mut ops, 3).unwrap();
+        let (lens, len) = lens(&chunked);
+        assert_eq!(lens[..len], [(false, 3), (false, 3)]);
+    }
+
+    #[test]
+    fn split_keeps_types_and_remainders() {
+        let mut read = [0; 5];
+        let mut ops = [Operation::Write(&[0; 2]), Operation::Read(&mut read)];
+        let chunked = split::<8, ()>(&mut ops, 2).unwrap();
+        let (lens, len) = lens(&chunked);
+        assert_eq!(lens[..len], [(false, 2), (true, 2), (true, 2), (true, 1)]);
+    }
+
+    #[test]
+    fn split_keeps_empty_operations() {
+        let mut read = [0; 0];
+        let mut ops = [
+            Operation::Write(&[]),
+            Operation::Write(&[0; 3]),
+            Operation::Read(&mut read),
+        ];
+        let chunked = split::<8, ()>(&mut ops, 2).unwrap();
+        let (lens, len) = lens(&chunked);
+        assert_eq!(lens[..len], [(false, 0), (false, 2), (false, 1), (true, 0)]);
+    }
+
+    #[test]
+    fn split_fills_capacity() {
+        let mut ops = [Operation::Write(&[0; 3]), Operation::Write(&[0])];
+        let chunked = split::<4, ()>(&mut ops, 1).unwrap();
+        assert_eq!(chunked.len(), 4);
+    }
+
+    #[test]
+    fn split_beyond_capacity() {
+        let mut ops = [Operation::Write(&[0; 3]), Operation::Write(&[0; 2])];
+        assert!(matches!(
+            split::<4, ()>(&mut ops, 1),
+            Err(ChunkedError::TooManyOperations)
+        ));
+    }
+
+    struct Bus;
+
+    impl Capabilities for Bus {
+        fn max_transfer_len(&self) -> Option<usize> {
+            Some(255)
+        }
+    }
+
+    #[test]
+    fn reports_max_transfer_len() {
+        assert_eq!(
+            ChunkedDevice::<_, 4>::new(Bus).max_transfer_len(),
+            Some(1020)
+        );
+        assert_eq!(
+            ChunkedDevice::<_, 4>::with_max_transfer_len(Bus, Some(usize::MAX)).max_transfer_len(),
+            Some(usize::MAX)
+        );
+        assert_eq!(
+            ChunkedDevice::<_, 4>::with_max_transfer_len(Bus, None).max_transfer_len(),
+            None
+        );
+    }
+}
@@ -17,3 +17,9 @@ pub use atomic::*;
 mod rc;
 #[cfg(feature = "alloc")]
 pub use rc::*;
+
+mod retry;
+pub use retry::*;
+
+mod chunked;
+pub use chunked::*;
@@ -0,0 +1,268 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{AddressMode, Error, ErrorKind, ErrorType, I2c, Operation};
+#[cfg(feature = "async")]
+use embedded_hal_async::{delay::DelayNs as AsyncDelayNs, i2c::I2c as AsyncI2c};
+
+/// [`I2c`] wrapper retrying transactions lost to arbitration, for multi-master buses.
+///
+/// When a transaction fails with [`ErrorKind::ArbitrationLoss`], another master won the bus and
+/// none of the transaction took effect, so it can be safely run again. `ArbitrationRetryDevice`
+/// waits for a backoff time and retries, up to a maximum number of retries. The backoff time
+/// doubles on every retry, saturating at `u32::MAX` microseconds.
+///
+/// The backoff has no jitter: masters using the same settings retry in lockstep, and may keep
+/// colliding. Give each master a different initial backoff time to avoid this.
+///
+/// Other errors, and the error of the last retry, are returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_hal::i2c::I2c;
+/// use embedded_hal_bus::i2c::ArbitrationRetryDevice;
+/// # use embedded_hal::delay::DelayNs;
+/// # use embedded_hal::i2c::{ErrorKind, ErrorType, Operation};
+/// # pub struct I2c0;
+/// # impl ErrorType for I2c0 { type Error = ErrorKind; }
+/// # impl I2c for I2c0 {
+/// #     fn transaction(&mut self, _: u8, _: &mut [Operation<'_>]) -> Result<(), Self::Error> { Ok(()) }
+/// # }
+/// # pub struct Delay;
+/// # impl DelayNs for Delay { fn delay_ns(&mut self, _: u32) {} }
+/// # let (i2c, delay) = (I2c0, Delay);
+///
+/// // Retry up to 5 times, waiting 100 µs, 200 µs, 400 µs... in between.
+/// let mut i2c = ArbitrationRetryDevice::new(i2c, delay, 5, 100);
+/// i2c.write(0x20, &[0x01, 0x02])?;
+/// # Ok::<(), ErrorKind>(())
+/// ```
+pub struct ArbitrationRetryDevice<BUS, D> {
+    bus: BUS,
+    delay: D,
+    max_retries: u8,
+    backoff_us: u32,
+}
+
+impl<BUS, D> ArbitrationRetryDevice<BUS, D> {
+    /// Create a new [`ArbitrationRetryDevice`].
+    ///
+    /// Transactions lost to arbitration are retried up to `max_retries` times. The first retry
+    /// happens after `backoff_us` microseconds, and this time doubles on every following retry.
+    #[inline]
+    pub fn new(bus: BUS, delay: D, max_retries: u8, backoff_us: u32) -> Self {
+        Self {
+            bus,
+            delay,
+            max_retries,
+            backoff_us,
+        }
+    }
+
+    /// Destroy the [`ArbitrationRetryDevice`], returning the bus and the delay.
+    #[inline]
+    pub fn into_inner(self) -> (BUS, D) {
+        (self.bus, self.delay)
+    }
+
+    #[inline]
+    fn backoff(&self) -> Backoff {
+        Backoff {
+            retries_left: self.max_retries,
+            next_us: self.backoff_us,
+        }
+    }
+}
+
+/// Retry state of a single transaction, shared by the blocking and async implementations.
+struct Backoff {
+    retries_left: u8,
+    next_us: u32,
+}
+
+impl Backoff {
+    /// Returns how long to wait before retrying after `error`, or `None` if the error must be
+    /// returned.
+    #[inline]
+    fn next(&mut self, error: &impl Error) -> Option<u32> {
+        if error.kind() != ErrorKind::ArbitrationLoss || self.retries_left == 0 {
+            return None;
+        }
+        let us = self.next_us;
+        self.next_us = us.saturating_mul(2);
+        self.retries_left -= 1;
+        Some(us)
+    }
+}
+
+impl<BUS: ErrorType, D> ErrorType for ArbitrationRetryDevice<BUS, D> {
+    type Error = BUS::Error;
+}
+
+impl<A: AddressMode, BUS: I2c<A>, D: DelayNs> I2c<A> for ArbitrationRetryDevice<BUS, D> {
+    fn transaction(
+        &mut self,
+        address: A,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut backoff = self.backoff();
+        loop {
+            match self.bus.transaction(address, operations) {
+                Err(e) => match backoff.next(&e) {
+                    Some(us) => self.delay.delay_us(us),
+                    None => return Err(e),
+                },
+                Ok(()) => return Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+impl<A: AddressMode, BUS: AsyncI2c<A>, D: AsyncDelayNs> AsyncI2c<A>
+    for ArbitrationRetryDevice<BUS, D>
+{
+    async fn transaction(
+        &mut self,
+        address: A,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut backoff = self.backoff();
+        loop {
+            match self.bus.transaction(address, operations).await {
+                Err(e) => match backoff.next(&e) {
+                    Some(us) => self.delay.delay_us(us).await,
+                    None => return Err(e),
+                },
+                Ok(()) => return Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bus failing with the scripted errors, then succeeding.
+    struct MockBus {
+        errors: &'static [ErrorKind],
+        calls: usize,
+    }
+
+    impl ErrorType for MockBus {
+        type Error = ErrorKind;
+    }
+
+    impl I2c for MockBus {
+        fn transaction(&mut self, _: u8, _: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+            let res = match self.errors.get(self.calls) {
+                Some(&e) => Err(e),
+                None => Ok(()),
+            };
+            self.calls += 1;
+            res
+        }
+    }
+
+    /// Delay recording the waits in microseconds.
+    #[derive(Default)]
+    struct MockDelay {
+        waits: [u32; 8],
+        len: usize,
+    }
+
+    impl MockDelay {
+        fn waits(&self) -> &[u32] {
+            &self.waits[..self.len]
+        }
+    }
+
+    impl DelayNs for MockDelay {
+        fn delay_ns(&mut self, _: u32) {
+            unreachable!()
+        }
+
+        fn delay_us(&mut self, us: u32) {
+            self.waits[self.len] = us;
+            self.len += 1;
+        }
+    }
+
+    const LOST: ErrorKind = ErrorKind::ArbitrationLoss;
+
+    fn device(
+        errors: &'static [ErrorKind],
+        max_retries: u8,
+        backoff_us: u32,
+    ) -> ArbitrationRetryDevice<MockBus, MockDelay> {
+        let bus = MockBus { errors, calls: 0 };
+        ArbitrationRetryDevice::new(bus, MockDelay::default(), max_retries, backoff_us)
+    }
+
+    #[test]
+    fn retries_with_doubling_backoff() {
+        let mut dev = device(&[LOST, LOST, LOST], 5, 100);
+        assert_eq!(dev.write(0x20, &[0]), Ok(()));
+
+        let (bus, delay) = dev.into_inner();
+        assert_eq!(bus.calls, 4);
+        assert_eq!(delay.waits(), [100, 200, 400]);
+    }
+
+    #[test]
+    fn gives_up_after_max_retries() {
+        let mut dev = device(&[LOST; 4], 3, 100);
+        assert_eq!(dev.write(0x20, &[0]), Err(LOST));
+
+        let (bus, delay) = dev.into_inner();
+        assert_eq!(bus.calls, 4);
+        assert_eq!(delay.waits(), [100, 200, 400]);
+    }
+
+    #[test]
+    fn zero_retries() {
+        let mut dev = device(&[LOST], 0, 100);
+        assert_eq!(dev.write(0x20, &[0]), Err(LOST));
+        assert_eq!(dev.into_inner().1.waits(), []);
+    }
+
+    #[test]
+    fn other_errors_are_not_retried() {
+        let mut dev = device(&[LOST, ErrorKind::Bus, LOST], 5, 100);
+        assert_eq!(dev.write(0x20, &[0]), Err(ErrorKind::Bus));
+
+        let (bus, delay) = dev.into_inner();
+        assert_eq!(bus.calls, 2);
+        assert_eq!(delay.waits(), [100]);
+    }
+
+    #[test]
+    fn backoff_saturates() {
+        let mut dev = device(&[LOST; 3], 3, u32::MAX / 2 + 1);
+        assert_eq!(dev.write(0x20, &[0]), Ok(()));
+        assert_eq!(
+            dev.into_inner().1.waits(),
+            [u32::MAX / 2 + 1, u32::MAX, u32::MAX]
+        );
+    }
+
+    #[test]
+    fn ten_bit_addresses() {
+        struct TenBitBus;
+
+        impl ErrorType for TenBitBus {
+            type Error = ErrorKind;
+        }
+
+        impl I2c<embedded_hal::i2c::TenBitAddress> for TenBitBus {
+            fn transaction(&mut self, _: u16, _: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+                Err(LOST)
+            }
+        }
+
+        let mut dev = ArbitrationRetryDevice::new(TenBitBus, MockDelay::default(), 2, 10);
+        assert_eq!(dev.write(0x123u16, &[0]), Err(LOST));
+        assert_eq!(dev.into_inner().1.waits(), [10, 20]);
+    }
+}
@@ -7,6 +7,11 @@
 #[cfg(feature = "defmt-03")]
 use defmt_03 as defmt;
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod explorer;
 pub mod i2c;
+pub mod mux;
+pub mod power;
 pub mod spi;
 pub mod util;
@@ -0,0 +1,38 @@
+use embedded_hal::digital::OutputPin;
+
+use super::MuxBackend;
+
+/// [`MuxBackend`] for analog switches driven by `N` GPIO select lines, such as the CD74HC4051.
+///
+/// The channel number is output in binary on the select lines, `pins[0]` being the least
+/// significant bit, giving `2^N` channels. The upstream bus isn't used to select a channel.
+pub struct GpioMux<P, const N: usize> {
+    pins: [P; N],
+}
+
+impl<P: OutputPin, const N: usize> GpioMux<P, N> {
+    /// Create a new [`GpioMux`] from its select lines, least significant bit first.
+    #[inline]
+    pub fn new(pins: [P; N]) -> Self {
+        Self { pins }
+    }
+
+    /// Destroy the [`GpioMux`], returning the select lines.
+    #[inline]
+    pub fn into_inner(self) -> [P; N] {
+        self.pins
+    }
+}
+
+impl<BUS, P: OutputPin, const N: usize> MuxBackend<BUS> for GpioMux<P, N> {
+    type Error = P::Error;
+
+    const CHANNELS: usize = 1 << N;
+
+    fn select(&mut self, _bus: &mut BUS, channel: usize) -> Result<(), Self::Error> {
+        for (bit, pin) in self.pins.iter_mut().enumerate() {
+            pin.set_state(((channel >> bit) & 1 == 1).into())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use embedded_hal::i2c::{ErrorType, I2c, Operation};
+
+use super::{Mux, MuxBackend, MuxError};
+
+/// [`I2c`] handle to one channel of a [`Mux`].
+///
+/// Created with [`Mux::i2c`].
+pub struct I2cChannel<'a, BUS, M> {
+    mux: &'a Mux<BUS, M>,
+    channel: usize,
+}
+
+impl<'a, BUS, M> I2cChannel<'a, BUS, M> {
+    pub(super) fn new(mux: &'a Mux<BUS, M>, channel: usize) -> Self {
+        Self { mux, channel }
+    }
+
+    /// The channel this handle talks to.
+    #[inline]
+    pub fn channel(&self) -> usize {
+        self.channel
+    }
+}
+
+impl<'a, BUS, M> ErrorType for I2cChannel<'a, BUS, M>
+where
+    BUS: I2c,
+    M: MuxBackend<BUS>,
+{
+    type Error = MuxError<BUS::Error, M::Error>;
+}
+
+impl<'a, BUS, M> I2c for I2cChannel<'a, BUS, M>
+where
+    BUS: I2c,
+    M: MuxBackend<BUS>,
+{
+    #[inline]
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.mux
+            .with_channel(self.channel, |bus| bus.read(address, read))
+    }
+
+    #[inline]
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        self.mux
+            .with_channel(self.channel, |bus| bus.write(address, write))
+    }
+
+    #[inline]
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.mux
+            .with_channel(self.channel, |bus| bus.write_read(address, write, read))
+    }
+
+    #[inline]
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.mux
+            .with_channel(self.channel, |bus| bus.transaction(address, operations))
+    }
+}
@@ -0,0 +1,351 @@
+//! Bus multiplexers.
+//!
+//! A bus multiplexer connects one upstream bus to one of several downstream bus segments
+//! (channels), e.g. to talk to several devices that have the same fixed I2C address.
+//! Multiplexers come in different forms: I2C switches such as the TCA9548 are programmed
+//! over the bus itself, while analog switches are driven by GPIO select lines.
+//!
+//! [`MuxBackend`] abstracts how a channel is selected. [`Mux`] owns the upstream bus and the
+//! backend, and hands out per-channel bus handles: [`I2cChannel`] implements [`I2c`](embedded_hal::i2c::I2c)
+//! and [`SpiChannel`] implements [`SpiDevice`](embedded_hal::spi::SpiDevice). Each handle switches
+//! the multiplexer to its channel before using the bus if needed. The currently selected channel is
+//! remembered, so consecutive operations on the same channel don't reprogram the multiplexer.
+//!
+//! Sharing is implemented with a `RefCell`, like in [`i2c::RefCellDevice`](crate::i2c::RefCellDevice),
+//! so handles can only be used within a single thread (interrupt priority level).
+//!
+//! # Examples
+//!
+//! Two sensors with the same address `0x76`, connected to channels 0 and 1 of a TCA9548:
+//!
+//! ```
+//! use embedded_hal_bus::mux::{Mux, Tca9548};
+//! # use embedded_hal::i2c::{self as hali2c, ErrorKind, I2c, Operation, SevenBitAddress};
+//! # pub struct Sensor<I2C> {
+//! #     i2c: I2C,
+//! #     address: u8,
+//! # }
+//! # impl<I2C: I2c> Sensor<I2C> {
+//! #     pub fn new(i2c: I2C, address: u8) -> Self {
+//! #         Self { i2c, address }
+//! #     }
+//! # }
+//! # pub struct I2c0;
+//! # impl hali2c::ErrorType for I2c0 {
+//! #     type Error = ErrorKind;
+//! # }
+//! # impl I2c<SevenBitAddress> for I2c0 {
+//! #     fn transaction(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+//! #       Ok(())
+//! #     }
+//! # }
+//! # let i2c = I2c0;
+//!
+//! let mux = Mux::new(i2c, Tca9548::new(Tca9548::DEFAULT_ADDRESS));
+//! let mut left = Sensor::new(mux.i2c(0), 0x76);
+//! let mut right = Sensor::new(mux.i2c(1), 0x76);
+//! ```
+
+use core::cell::RefCell;
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[cfg(feature = "defmt-03")]
+use crate::defmt;
+
+mod gpio;
+mod i2c;
+mod spi;
+mod tca9548;
+pub use gpio::*;
+pub use i2c::*;
+pub use spi::*;
+pub use tca9548::*;
+
+/// Channel selection mechanism of a bus multiplexer.
+pub trait MuxBackend<BUS> {
+    /// Error returned when selecting a channel fails.
+    type Error: Debug;
+
+    /// Number of downstream channels.
+    const CHANNELS: usize;
+
+    /// Connect the upstream `bus` to downstream `channel`.
+    ///
+    /// `channel` is always lower than [`CHANNELS`](MuxBackend::CHANNELS). Backends that are
+    /// programmed over the bus itself use `bus` to do so, others ignore it.
+    fn select(&mut self, bus: &mut BUS, channel: usize) -> Result<(), Self::Error>;
+}
+
+/// Error type for [`I2cChannel`] and [`SpiChannel`] operations.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum MuxError<BUS, SEL> {
+    /// An operation on the selected channel failed.
+    Bus(BUS),
+    /// Selecting the channel failed.
+    Select(SEL),
+}
+
+impl<BUS: Display, SEL: Display> Display for MuxError<BUS, SEL> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Bus(bus) => write!(f, "Bus error: {}", bus),
+            Self::Select(sel) => write!(f, "Mux channel selection error: {}", sel),
+        }
+    }
+}
+
+impl<BUS: Debug + Display, SEL: Debug + Display> core::error::Error for MuxError<BUS, SEL> {}
+
+impl<BUS, SEL> embedded_hal::i2c::Error for MuxError<BUS, SEL>
+where
+    BUS: embedded_hal::i2c::Error,
+    SEL: Debug,
+{
+    #[inline]
+    fn kind(&self) -> embedded_hal::i2c::ErrorKind {
+        match self {
+            Self::Bus(e) => e.kind(),
+            Self::Select(_) => embedded_hal::i2c::ErrorKind::Other,
+        }
+    }
+}
+
+impl<BUS, SEL> embedded_hal::spi::Error for MuxError<BUS, SEL>
+where
+    BUS: embedded_hal::spi::Error,
+    SEL: Debug,
+{
+    #[inline]
+    fn kind(&self) -> embedded_hal::spi::ErrorKind {
+        match self {
+            Self::Bus(e) => e.kind(),
+            Self::Select(_) => embedded_hal::spi::ErrorKind::Other,
+        }
+    }
+}
+
+/// Bus multiplexer, handing out per-channel bus handles.
+///
+/// See the [module-level documentation](self) for details.
+pub struct Mux<BUS, M> {
+    inner: RefCell<Inner<BUS, M>>,
+}
+
+struct Inner<BUS, M> {
+    bus: BUS,
+    backend: M,
+    selected: Option<usize>,
+}
+
+impl<BUS, M: MuxBackend<BUS>> Mux<BUS, M> {
+    /// Create a new [`Mux`].
+    ///
+    /// No channel is assumed to be selected, so the first operation on any channel
+    /// will select it.
+    #[inline]
+    pub fn new(bus: BUS, backend: M) -> Self {
+        Self {
+            inner: RefCell::new(Inner {
+                bus,
+                backend,
+                selected: None,
+            }),
+        }
+    }
+
+    /// Destroy the [`Mux`], returning the upstream bus and the backend.
+    #[inline]
+    pub fn into_inner(self) -> (BUS, M) {
+        let inner = self.inner.into_inner();
+        (inner.bus, inner.backend)
+    }
+
+    /// Get an [`I2c`](embedded_hal::i2c::I2c) handle to `channel`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is not lower than [`M::CHANNELS`](MuxBackend::CHANNELS).
+    #[inline]
+    pub fn i2c(&self, channel: usize) -> I2cChannel<'_, BUS, M> {
+        I2cChannel::new(self, check_channel::<BUS, M>(channel))
+    }
+
+    /// Get an [`SpiDevice`](embedded_hal::spi::SpiDevice) handle to a device on `channel`.
+    ///
+    /// This sets the `cs` pin high, and returns an error if that fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is not lower than [`M::CHANNELS`](MuxBackend::CHANNELS).
+    #[inline]
+    pub fn spi<CS, D>(
+        &self,
+        channel: usize,
+        cs: CS,
+        delay: D,
+    ) -> Result<SpiChannel<'_, BUS, M, CS, D>, CS::Error>
+    where
+        CS: embedded_hal::digital::OutputPin,
+    {
+        SpiChannel::new(self, check_channel::<BUS, M>(channel), cs, delay)
+    }
+
+    /// Run `f` on the upstream bus, after selecting `channel` if it isn't already selected.
+    fn with_channel<R, E>(
+        &self,
+        channel: usize,
+        f: impl FnOnce(&mut BUS) -> Result<R, E>,
+    ) -> Result<R, MuxError<E, M::Error>> {
+        let inner = &mut *self.inner.borrow_mut();
+
+        if inner.selected != Some(channel) {
+            // The multiplexer state is unknown if selection fails, so select again next time.
+            inner.selected = None;
+            inner
+                .backend
+                .select(&mut inner.bus, channel)
+                .map_err(MuxError::Select)?;
+            inner.selected = Some(channel);
+        }
+
+        f(&mut inner.bus).map_err(MuxError::Bus)
+    }
+}
+
+fn check_channel<BUS, M: MuxBackend<BUS>>(channel: usize) -> usize {
+    assert!(
+        channel < M::CHANNELS,
+        "mux channel {} out of range, the multiplexer has {} channels",
+        channel,
+        M::CHANNELS
+    );
+    channel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, Operation};
+
+    /// I2C bus recording the channel selected at the time of each transaction.
+    #[derive(Default)]
+    struct MockBus {
+        channel: Option<usize>,
+        transactions: [Option<usize>; 8],
+        len: usize,
+    }
+
+    impl MockBus {
+        fn transactions(&self) -> &[Option<usize>] {
+            &self.transactions[..self.len]
+        }
+    }
+
+    impl ErrorType for MockBus {
+        type Error = ErrorKind;
+    }
+
+    impl I2c for MockBus {
+        fn transaction(&mut self, _: u8, _: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+            self.transactions[self.len] = self.channel;
+            self.len += 1;
+            Ok(())
+        }
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+    struct SelectError;
+
+    /// Backend recording every selection, and failing the next one if asked to.
+    #[derive(Default)]
+    struct RecordingBackend {
+        selects: [usize; 8],
+        len: usize,
+        fail: bool,
+    }
+
+    impl RecordingBackend {
+        fn selects(&self) -> &[usize] {
+            &self.selects[..self.len]
+        }
+    }
+
+    impl MuxBackend<MockBus> for RecordingBackend {
+        type Error = SelectError;
+
+        const CHANNELS: usize = 4;
+
+        fn select(&mut self, bus: &mut MockBus, channel: usize) -> Result<(), Self::Error> {
+            self.selects[self.len] = channel;
+            self.len += 1;
+            if self.fail {
+                self.fail = false;
+                bus.channel = None;
+                return Err(SelectError);
+            }
+            bus.channel = Some(channel);
+            Ok(())
+        }
+    }
+
+    fn mux() -> Mux<MockBus, RecordingBackend> {
+        Mux::new(MockBus::default(), RecordingBackend::default())
+    }
+
+    fn fail_next_select(mux: &Mux<MockBus, RecordingBackend>) {
+        mux.inner.borrow_mut().backend.fail = true;
+    }
+
+    #[test]
+    fn same_channel_selected_once() {
+        let mux = mux();
+        let mut ch = mux.i2c(1);
+        ch.write(0x20, &[0]).unwrap();
+        ch.write(0x20, &[0]).unwrap();
+        mux.i2c(1).write(0x20, &[0]).unwrap();
+
+        let (bus, backend) = mux.into_inner();
+        assert_eq!(backend.selects(), [1]);
+        assert_eq!(bus.transactions(), [Some(1); 3]);
+    }
+
+    #[test]
+    fn channel_change_reselects() {
+        let mux = mux();
+        let (mut ch0, mut ch2) = (mux.i2c(0), mux.i2c(2));
+        ch0.write(0x20, &[0]).unwrap();
+        ch2.write(0x20, &[0]).unwrap();
+        ch0.write(0x20, &[0]).unwrap();
+
+        let (bus, backend) = mux.into_inner();
+        assert_eq!(backend.selects(), [0, 2, 0]);
+        assert_eq!(bus.transactions(), [Some(0), Some(2), Some(0)]);
+    }
+
+    #[test]
+    fn failed_select_is_reported_and_retried() {
+        let mux = mux();
+        let mut ch = mux.i2c(3);
+        ch.write(0x20, &[0]).unwrap();
+
+        let mut other = mux.i2c(1);
+        fail_next_select(&mux);
+        assert_eq!(other.write(0x20, &[0]), Err(MuxError::Select(SelectError)));
+        assert_eq!(mux.inner.borrow().selected, None);
+
+        // The mux state is unknown after the failure, so even the previous channel is selected
+        // again.
+        ch.write(0x20, &[0]).unwrap();
+
+        let (bus, backend) = mux.into_inner();
+        assert_eq!(backend.selects(), [3, 1, 3]);
+        assert_eq!(bus.transactions(), [Some(3), Some(3)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn channel_out_of_range() {
+        mux().i2c(4);
+    }
+}
@@ -0,0 +1,67 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::{ErrorType, Operation, SpiBus, SpiDevice};
+
+use super::{Mux, MuxBackend, MuxError};
+use crate::spi::shared::transaction;
+use crate::spi::DeviceError;
+
+/// [`SpiDevice`] handle to a device on one channel of a [`Mux`].
+///
+/// Created with [`Mux::spi`].
+pub struct SpiChannel<'a, BUS, M, CS, D> {
+    mux: &'a Mux<BUS, M>,
+    channel: usize,
+    cs: CS,
+    delay: D,
+}
+
+impl<'a, BUS, M, CS, D> SpiChannel<'a, BUS, M, CS, D> {
+    pub(super) fn new(
+        mux: &'a Mux<BUS, M>,
+        channel: usize,
+        mut cs: CS,
+        delay: D,
+    ) -> Result<Self, CS::Error>
+    where
+        CS: OutputPin,
+    {
+        cs.set_high()?;
+        Ok(Self {
+            mux,
+            channel,
+            cs,
+            delay,
+        })
+    }
+
+    /// The channel this handle talks to.
+    #[inline]
+    pub fn channel(&self) -> usize {
+        self.channel
+    }
+}
+
+impl<'a, BUS, M, CS, D> ErrorType for SpiChannel<'a, BUS, M, CS, D>
+where
+    BUS: ErrorType,
+    M: MuxBackend<BUS>,
+    CS: OutputPin,
+{
+    type Error = MuxError<DeviceError<BUS::Error, CS::Error>, M::Error>;
+}
+
+impl<'a, Word: Copy + 'static, BUS, M, CS, D> SpiDevice<Word> for SpiChannel<'a, BUS, M, CS, D>
+where
+    BUS: SpiBus<Word>,
+    M: MuxBackend<BUS>,
+    CS: OutputPin,
+    D: DelayNs,
+{
+    #[inline]
+    fn transaction(&mut self, operations: &mut [Operation<'_, Word>]) -> Result<(), Self::Error> {
+        let (cs, delay) = (&mut self.cs, &mut self.delay);
+        self.mux
+            .with_channel(self.channel, |bus| transaction(operations, bus, delay, cs))
+    }
+}
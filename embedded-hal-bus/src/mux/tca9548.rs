@@ -0,0 +1,33 @@
+use embedded_hal::i2c::I2c;
+
+use super::MuxBackend;
+
+/// [`MuxBackend`] for the TCA9548A / PCA9548A 8-channel I2C switch.
+///
+/// The switch is programmed over the upstream I2C bus. Only one channel is enabled at a time.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Tca9548 {
+    address: u8,
+}
+
+impl Tca9548 {
+    /// Address of the switch with all address pins tied low.
+    pub const DEFAULT_ADDRESS: u8 = 0x70;
+
+    /// Create a new [`Tca9548`] backend for the switch at `address`.
+    #[inline]
+    pub fn new(address: u8) -> Self {
+        Self { address }
+    }
+}
+
+impl<BUS: I2c> MuxBackend<BUS> for Tca9548 {
+    type Error = BUS::Error;
+
+    const CHANNELS: usize = 8;
+
+    #[inline]
+    fn select(&mut self, bus: &mut BUS, channel: usize) -> Result<(), Self::Error> {
+        bus.write(self.address, &[1 << channel])
+    }
+}
@@ -0,0 +1,300 @@
+//! Power-gated devices.
+
+use core::fmt::{self, Debug, Display, Formatter};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::{i2c, spi};
+#[cfg(feature = "async")]
+use embedded_hal_async::{
+    delay::DelayNs as AsyncDelayNs, i2c::I2c as AsyncI2c, spi::SpiDevice as AsyncSpiDevice,
+};
+
+#[cfg(feature = "defmt-03")]
+use crate::defmt;
+
+/// Error type for [`PowerGated`] operations.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum PowerGatedError<DEV, PIN> {
+    /// An operation on the wrapped device failed.
+    Device(DEV),
+    /// Driving the enable pin failed.
+    Pin(PIN),
+}
+
+impl<DEV: Display, PIN: Display> Display for PowerGatedError<DEV, PIN> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Device(dev) => write!(f, "Device error: {}", dev),
+            Self::Pin(pin) => write!(f, "Enable pin error: {}", pin),
+        }
+    }
+}
+
+impl<DEV: Debug + Display, PIN: Debug + Display> core::error::Error for PowerGatedError<DEV, PIN> {}
+
+impl<DEV, PIN> i2c::Error for PowerGatedError<DEV, PIN>
+where
+    DEV: i2c::Error,
+    PIN: Debug,
+{
+    #[inline]
+    fn kind(&self) -> i2c::ErrorKind {
+        match self {
+            Self::Device(e) => e.kind(),
+            Self::Pin(_) => i2c::ErrorKind::Other,
+        }
+    }
+}
+
+impl<DEV, PIN> spi::Error for PowerGatedError<DEV, PIN>
+where
+    DEV: spi::Error,
+    PIN: Debug,
+{
+    #[inline]
+    fn kind(&self) -> spi::ErrorKind {
+        match self {
+            Self::Device(e) => e.kind(),
+            Self::Pin(_) => spi::ErrorKind::Other,
+        }
+    }
+}
+
+/// Device wrapper that powers the device up only for the duration of each transaction.
+///
+/// Before each transaction, the `enable` pin (e.g. the enable input of a load switch or
+/// regulator) is driven high, and `delay` is used to wait for the configured settle time.
+/// After the transaction, the pin is driven low again, even if the transaction failed.
+///
+/// With the async implementations, dropping the future before it completes (e.g. on a timeout)
+/// also drives the pin low, since the transaction can't be resumed. Errors driving the pin low
+/// can't be reported in that case, and are ignored.
+///
+/// This is a common pattern for battery-powered sensor nodes. Note that the device loses
+/// its state between transactions, so it's best suited to devices that are fully configured
+/// and read within a single transaction.
+///
+/// `PowerGated` implements [`I2c`](i2c::I2c) if `DEV` does, and [`SpiDevice`](spi::SpiDevice)
+/// if `DEV` does.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_hal::i2c::I2c;
+/// use embedded_hal_bus::power::PowerGated;
+/// # use core::convert::Infallible;
+/// # use embedded_hal::delay::DelayNs;
+/// # use embedded_hal::digital::{ErrorType as PinErrorType, OutputPin};
+/// # use embedded_hal::i2c::{ErrorKind, ErrorType, Operation};
+/// # pub struct I2c0;
+/// # impl ErrorType for I2c0 { type Error = ErrorKind; }
+/// # impl I2c for I2c0 {
+/// #     fn transaction(&mut self, _: u8, _: &mut [Operation<'_>]) -> Result<(), Self::Error> { Ok(()) }
+/// # }
+/// # pub struct Pin;
+/// # impl PinErrorType for Pin { type Error = Infallible; }
+/// # impl OutputPin for Pin {
+/// #     fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// #     fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// # }
+/// # pub struct Delay;
+/// # impl DelayNs for Delay { fn delay_ns(&mut self, _: u32) {} }
+/// # let (i2c, sensor_power, delay) = (I2c0, Pin, Delay);
+///
+/// // The sensor needs 2 ms after power-up before it answers.
+/// let mut sensor = PowerGated::new(i2c, sensor_power, delay, 2_000)?;
+///
+/// let mut temperature = [0; 2];
+/// sensor.write_read(0x48, &[0x00], &mut temperature).unwrap();
+/// # Ok::<(), Infallible>(())
+/// ```
+pub struct PowerGated<DEV, PIN, D> {
+    device: DEV,
+    enable: PIN,
+    delay: D,
+    settle_us: u32,
+}
+
+impl<DEV, PIN, D> PowerGated<DEV, PIN, D> {
+    /// Create a new [`PowerGated`] device.
+    ///
+    /// `settle_us` is the time to wait after powering the device up, in microseconds.
+    ///
+    /// This drives the `enable` pin low, and returns an error if that fails.
+    #[inline]
+    pub fn new(device: DEV, mut enable: PIN, delay: D, settle_us: u32) -> Result<Self, PIN::Error>
+    where
+        PIN: OutputPin,
+    {
+        enable.set_low()?;
+        Ok(Self {
+            device,
+            enable,
+            delay,
+            settle_us,
+        })
+    }
+
+    /// Returns a reference to the wrapped device.
+    ///
+    /// The device is powered down outside of transactions.
+    #[inline]
+    pub fn device(&self) -> &DEV {
+        &self.device
+    }
+
+    /// Returns a mutable reference to the wrapped device.
+    ///
+    /// The device is powered down outside of transactions.
+    #[inline]
+    pub fn device_mut(&mut self) -> &mut DEV {
+        &mut self.device
+    }
+
+    /// Destroy the [`PowerGated`] device, returning the wrapped device, the enable pin and the delay.
+    #[inline]
+    pub fn into_inner(self) -> (DEV, PIN, D) {
+        (self.device, self.enable, self.delay)
+    }
+}
+
+impl<DEV, PIN: OutputPin, D: DelayNs> PowerGated<DEV, PIN, D> {
+    /// Run `f` with the device powered up.
+    fn powered<E>(
+        &mut self,
+        f: impl FnOnce(&mut DEV) -> Result<(), E>,
+    ) -> Result<(), PowerGatedError<E, PIN::Error>> {
+        let power = PowerGuard::on(&mut self.enable)?;
+        self.delay.delay_us(self.settle_us);
+
+        let res = f(&mut self.device);
+        power.off(res)
+    }
+}
+
+/// Keeps the device powered up while alive.
+///
+/// Dropping the guard without calling [`off`](PowerGuard::off), e.g. when a transaction future
+/// is cancelled, powers the device down too.
+struct PowerGuard<'a, PIN: OutputPin> {
+    enable: &'a mut PIN,
+}
+
+impl<'a, PIN: OutputPin> PowerGuard<'a, PIN> {
+    #[inline]
+    fn on<E>(enable: &'a mut PIN) -> Result<Self, PowerGatedError<E, PIN::Error>> {
+        enable.set_high().map_err(PowerGatedError::Pin)?;
+        Ok(Self { enable })
+    }
+
+    /// Power the device down, and combine the result of the transaction with the pin result.
+    #[inline]
+    fn off<E>(self, res: Result<(), E>) -> Result<(), PowerGatedError<E, PIN::Error>> {
+        // On failure, it's important to still power down.
+        let pin_res = self.enable.set_low();
+        core::mem::forget(self);
+
+        res.map_err(PowerGatedError::Device)?;
+        pin_res.map_err(PowerGatedError::Pin)
+    }
+}
+
+impl<PIN: OutputPin> Drop for PowerGuard<'_, PIN> {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = self.enable.set_low();
+    }
+}
+
+impl<DEV, PIN, D> i2c::ErrorType for PowerGated<DEV, PIN, D>
+where
+    DEV: i2c::ErrorType,
+    PIN: OutputPin,
+{
+    type Error = PowerGatedError<DEV::Error, PIN::Error>;
+}
+
+impl<A, DEV, PIN, D> i2c::I2c<A> for PowerGated<DEV, PIN, D>
+where
+    A: i2c::AddressMode,
+    DEV: i2c::I2c<A>,
+    PIN: OutputPin,
+    D: DelayNs,
+{
+    #[inline]
+    fn transaction(
+        &mut self,
+        address: A,
+        operations: &mut [i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.powered(|device| device.transaction(address, operations))
+    }
+}
+
+impl<DEV, PIN, D> spi::ErrorType for PowerGated<DEV, PIN, D>
+where
+    DEV: spi::ErrorType,
+    PIN: OutputPin,
+{
+    type Error = PowerGatedError<DEV::Error, PIN::Error>;
+}
+
+impl<Word: Copy + 'static, DEV, PIN, D> spi::SpiDevice<Word> for PowerGated<DEV, PIN, D>
+where
+    DEV: spi::SpiDevice<Word>,
+    PIN: OutputPin,
+    D: DelayNs,
+{
+    #[inline]
+    fn transaction(
+        &mut self,
+        operations: &mut [spi::Operation<'_, Word>],
+    ) -> Result<(), Self::Error> {
+        self.powered(|device| device.transaction(operations))
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+impl<A, DEV, PIN, D> AsyncI2c<A> for PowerGated<DEV, PIN, D>
+where
+    A: i2c::AddressMode,
+    DEV: AsyncI2c<A>,
+    PIN: OutputPin,
+    D: AsyncDelayNs,
+{
+    #[inline]
+    async fn transaction(
+        &mut self,
+        address: A,
+        operations: &mut [i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let power = PowerGuard::on(&mut self.enable)?;
+        self.delay.delay_us(self.settle_us).await;
+
+        let res = self.device.transaction(address, operations).await;
+        power.off(res)
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+impl<Word: Copy + 'static, DEV, PIN, D> AsyncSpiDevice<Word> for PowerGated<DEV, PIN, D>
+where
+    DEV: AsyncSpiDevice<Word>,
+    PIN: OutputPin,
+    D: AsyncDelayNs,
+{
+    #[inline]
+    async fn transaction(
+        &mut self,
+        operations: &mut [spi::Operation<'_, Word>],
+    ) -> Result<(), Self::Error> {
+        let power = PowerGuard::on(&mut self.enable)?;
+        self.delay.delay_us(self.settle_us).await;
+
+        let res = self.device.transaction(operations).await;
+        power.off(res)
+    }
+}
@@ -0,0 +1,235 @@
+use core::ops::Range;
+use embedded_hal::spi::{Capabilities, ErrorType, SpiBus};
+#[cfg(feature = "async")]
+use embedded_hal_async::spi::SpiBus as AsyncSpiBus;
+
+/// [`SpiBus`] wrapper splitting transfers longer than the hardware supports.
+///
+/// Some SPI peripherals (typically when using DMA) can only transfer a limited number of words
+/// at once. `ChunkedBus` splits every read, write and transfer into consecutive transfers of at
+/// most the maximum length. Since chip select is handled by the [`SpiDevice`](embedded_hal::spi::SpiDevice)
+/// on top of the bus, the device sees the same clock and data as for a single transfer.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_hal::spi::SpiBus;
+/// use embedded_hal_bus::spi::ChunkedBus;
+/// # use embedded_hal::spi::{Capabilities, ErrorKind, ErrorType};
+/// # pub struct Spi0;
+/// # impl ErrorType for Spi0 { type Error = ErrorKind; }
+/// # impl SpiBus for Spi0 {
+/// #     fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> { assert!(words.len() <= 255); Ok(()) }
+/// #     fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> { assert!(words.len() <= 255); Ok(()) }
+/// #     fn transfer(&mut self, _: &mut [u8], _: &[u8]) -> Result<(), Self::Error> { Ok(()) }
+/// #     fn transfer_in_place(&mut self, _: &mut [u8]) -> Result<(), Self::Error> { Ok(()) }
+/// #     fn flush(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// # }
+/// # impl Capabilities for Spi0 {
+/// #     fn max_transfer_len(&self) -> Option<usize> { Some(255) }
+/// # }
+/// # let spi = Spi0;
+///
+/// // `spi` reports a maximum transfer length through `Capabilities`.
+/// let mut spi = ChunkedBus::new(spi);
+/// spi.write(&[0; 1024])?;
+/// # Ok::<(), ErrorKind>(())
+/// ```
+pub struct ChunkedBus<BUS> {
+    bus: BUS,
+    max_len: Option<usize>,
+}
+
+impl<BUS> ChunkedBus<BUS> {
+    /// Create a new [`ChunkedBus`], using the maximum transfer length reported by `bus`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bus` reports a maximum transfer length of 0.
+    #[inline]
+    pub fn new(bus: BUS) -> Self
+    where
+        BUS: Capabilities,
+    {
+        let max_len = bus.max_transfer_len();
+        Self::with_max_transfer_len(bus, max_len)
+    }
+
+    /// Create a new [`ChunkedBus`] with an explicit maximum transfer length, in words.
+    ///
+    /// `None` disables chunking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is `Some(0)`.
+    #[inline]
+    pub fn with_max_transfer_len(bus: BUS, max_len: Option<usize>) -> Self {
+        assert!(max_len != Some(0), "maximum transfer length must not be 0");
+        Self { bus, max_len }
+    }
+
+    /// Destroy the [`ChunkedBus`], returning the wrapped bus.
+    #[inline]
+    pub fn into_inner(self) -> BUS {
+        self.bus
+    }
+}
+
+impl<BUS: ErrorType> ErrorType for ChunkedBus<BUS> {
+    type Error = BUS::Error;
+}
+
+impl<Word: Copy + 'static, BUS: SpiBus<Word>> SpiBus<Word> for ChunkedBus<BUS> {
+    fn read(&mut self, words: &mut [Word]) -> Result<(), Self::Error> {
+        match self.max_len {
+            Some(max_len) => words
+                .chunks_mut(max_len)
+                .try_for_each(|chunk| self.bus.read(chunk)),
+            None => self.bus.read(words),
+        }
+    }
+
+    fn write(&mut self, words: &[Word]) -> Result<(), Self::Error> {
+        match self.max_len {
+            Some(max_len) => words
+                .chunks(max_len)
+                .try_for_each(|chunk| self.bus.write(chunk)),
+            None => self.bus.write(words),
+        }
+    }
+
+    fn transfer(&mut self, read: &mut [Word], write: &[Word]) -> Result<(), Self::Error> {
+        let Some(max_len) = self.max_len else {
+            return self.bus.transfer(read, write);
+        };
+
+        for (r, w) in transfer_chunks(read.len(), write.len(), max_len) {
+            self.bus.transfer(&mut read[r], &write[w])?;
+        }
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, words: &mut [Word]) -> Result<(), Self::Error> {
+        match self.max_len {
+            Some(max_len) => words
+                .chunks_mut(max_len)
+                .try_for_each(|chunk| self.bus.transfer_in_place(chunk)),
+            None => self.bus.transfer_in_place(words),
+        }
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.bus.flush()
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+impl<Word: Copy + 'static, BUS: AsyncSpiBus<Word>> AsyncSpiBus<Word> for ChunkedBus<BUS> {
+    async fn read(&mut self, words: &mut [Word]) -> Result<(), Self::Error> {
+        let Some(max_len) = self.max_len else {
+            return self.bus.read(words).await;
+        };
+        for chunk in words.chunks_mut(max_len) {
+            self.bus.read(chunk).await?;
+        }
+        Ok(())
+    }
+
+    async fn write(&mut self, words: &[Word]) -> Result<(), Self::Error> {
+        let Some(max_len) = self.max_len else {
+            return self.bus.write(words).await;
+        };
+        for chunk in words.chunks(max_len) {
+            self.bus.write(chunk).await?;
+        }
+        Ok(())
+    }
+
+    async fn transfer(&mut self, read: &mut [Word], write: &[Word]) -> Result<(), Self::Error> {
+        let Some(max_len) = self.max_len else {
+            return self.bus.transfer(read, write).await;
+        };
+        for (r, w) in transfer_chunks(read.len(), write.len(), max_len) {
+            self.bus.transfer(&mut read[r], &write[w]).await?;
+        }
+        Ok(())
+    }
+
+    async fn transfer_in_place(&mut self, words: &mut [Word]) -> Result<(), Self::Error> {
+        let Some(max_len) = self.max_len else {
+            return self.bus.transfer_in_place(words).await;
+        };
+        for chunk in words.chunks_mut(max_len) {
+            self.bus.transfer_in_place(chunk).await?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.bus.flush().await
+    }
+}
+
+impl<BUS: Capabilities> Capabilities for ChunkedBus<BUS> {
+    #[inline]
+    fn max_frequency_hz(&self) -> Option<u32> {
+        self.bus.max_frequency_hz()
+    }
+
+    #[inline]
+    fn has_dma(&self) -> bool {
+        self.bus.has_dma()
+    }
+}
+
+/// Split a transfer of `read_len` and `write_len` words into chunks of at most `max_len` words,
+/// returning the read and write ranges of each chunk.
+///
+/// The shorter buffer runs out first, its ranges are empty from then on.
+fn transfer_chunks(
+    read_len: usize,
+    write_len: usize,
+    max_len: usize,
+) -> impl Iterator<Item = (Range<usize>, Range<usize>)> {
+    let len = read_len.max(write_len);
+    (0..len).step_by(max_len).map(move |start| {
+        let end = (start + max_len).min(len);
+        (
+            start.min(read_len)..end.min(read_len),
+            start.min(write_len)..end.min(write_len),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfer_exact_multiple() {
+        assert!(transfer_chunks(4, 4, 2).eq([(0..2, 0..2), (2..4, 2..4)]));
+    }
+
+    #[test]
+    fn transfer_read_longer() {
+        assert!(transfer_chunks(5, 2, 2).eq([(0..2, 0..2), (2..4, 2..2), (4..5, 2..2)]));
+    }
+
+    #[test]
+    fn transfer_write_longer() {
+        assert!(transfer_chunks(1, 4, 2).eq([(0..1, 0..2), (1..1, 2..4)]));
+    }
+
+    #[test]
+    fn transfer_shorter_than_max() {
+        assert!(transfer_chunks(3, 1, 8).eq([(0..3, 0..1)]));
+    }
+
+    #[test]
+    fn transfer_empty() {
+        assert_eq!(transfer_chunks(0, 0, 2).count(), 0);
+    }
+}
@@ -3,6 +3,10 @@
 use core::fmt::{self, Debug, Display, Formatter};
 use embedded_hal::spi::{Error, ErrorKind};
 
+mod chunked;
+pub use chunked::*;
+mod retry;
+pub use retry::*;
 mod exclusive;
 pub use exclusive::*;
 mod refcell;
@@ -14,9 +18,11 @@ pub use mutex::*;
 #[cfg(any(feature = "portable-atomic", target_has_atomic = "8"))]
 mod atomic;
 mod critical_section;
-mod shared;
+pub(crate) mod shared;
+mod shift_register;
 #[cfg(any(feature = "portable-atomic", target_has_atomic = "8"))]
 pub use atomic::*;
+pub use shift_register::*;
 
 #[cfg(feature = "alloc")]
 mod rc;
@@ -0,0 +1,165 @@
+use core::fmt::{self, Debug, Display, Formatter};
+use embedded_hal::spi::{Error, ErrorKind, ErrorType, Operation, SpiDevice};
+#[cfg(feature = "async")]
+use embedded_hal_async::spi::SpiDevice as AsyncSpiDevice;
+
+#[cfg(feature = "defmt-03")]
+use crate::defmt;
+
+/// Error type for [`RetryDevice`] operations.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum RetryError<DEV> {
+    /// An operation on the wrapped device failed.
+    Device(DEV),
+    /// The response was still invalid after the last attempt.
+    Invalid,
+}
+
+impl<DEV: Display> Display for RetryError<DEV> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Device(dev) => write!(f, "SPI device error: {}", dev),
+            Self::Invalid => write!(f, "Invalid response after all attempts"),
+        }
+    }
+}
+
+impl<DEV: Debug + Display> core::error::Error for RetryError<DEV> {}
+
+impl<DEV: Error> Error for RetryError<DEV> {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Device(e) => e.kind(),
+            Self::Invalid => ErrorKind::Other,
+        }
+    }
+}
+
+/// [`SpiDevice`] wrapper re-running transactions whose response fails validation.
+///
+/// Many sensor protocols protect responses with a CRC, and expect the host to simply retry the
+/// transaction when it doesn't match. `RetryDevice` runs each transaction on the wrapped device,
+/// then calls the validator with the completed operations. If the validator returns `false`, the
+/// transaction is run again, up to a total of `attempts` times.
+///
+/// Transactions that fail with a device error are not retried.
+///
+/// # Transactions with [`Operation::TransferInPlace`]
+///
+/// A [`TransferInPlace`](Operation::TransferInPlace) operation replaces the data to send with the
+/// received data, so running the transaction again would send the response of the previous attempt.
+/// Transactions containing such an operation are therefore run only once: an invalid response is
+/// reported as [`RetryError::Invalid`] straight away. Use [`Operation::Transfer`] with separate
+/// buffers for transactions that need retrying.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_hal::spi::{Operation, SpiDevice};
+/// use embedded_hal_bus::spi::RetryDevice;
+/// # use embedded_hal::spi::{ErrorKind, ErrorType};
+/// # pub struct Sensor;
+/// # impl ErrorType for Sensor { type Error = ErrorKind; }
+/// # impl SpiDevice for Sensor {
+/// #     fn transaction(&mut self, _: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> { Ok(()) }
+/// # }
+/// # let spi = Sensor;
+/// # fn crc8(_: &[u8]) -> u8 { 0 }
+///
+/// // The last byte of every response is a CRC of the bytes before it.
+/// let mut spi = RetryDevice::new(spi, 3, |operations: &[Operation<'_, u8>]| {
+///     operations.iter().all(|op| match op {
+///         Operation::Read(buf) => crc8(&buf[..buf.len() - 1]) == buf[buf.len() - 1],
+///         _ => true,
+///     })
+/// });
+///
+/// let mut response = [0; 3];
+/// spi.transaction(&mut [Operation::Write(&[0x80]), Operation::Read(&mut response)])
+///     .unwrap();
+/// ```
+pub struct RetryDevice<DEV, F> {
+    device: DEV,
+    attempts: u8,
+    validator: F,
+}
+
+impl<DEV, F> RetryDevice<DEV, F> {
+    /// Create a new [`RetryDevice`].
+    ///
+    /// Transactions are run up to `attempts` times until `validator` accepts the response.
+    /// An `attempts` value of 0 is treated as 1.
+    #[inline]
+    pub fn new(device: DEV, attempts: u8, validator: F) -> Self {
+        Self {
+            device,
+            attempts: attempts.max(1),
+            validator,
+        }
+    }
+
+    /// Destroy the [`RetryDevice`], returning the wrapped device and the validator.
+    #[inline]
+    pub fn into_inner(self) -> (DEV, F) {
+        (self.device, self.validator)
+    }
+
+    fn attempts<Word: 'static>(&self, operations: &[Operation<'_, Word>]) -> u8 {
+        if operations
+            .iter()
+            .any(|op| matches!(op, Operation::TransferInPlace(_)))
+        {
+            1
+        } else {
+            self.attempts
+        }
+    }
+}
+
+impl<DEV: ErrorType, F> ErrorType for RetryDevice<DEV, F> {
+    type Error = RetryError<DEV::Error>;
+}
+
+impl<Word: Copy + 'static, DEV, F> SpiDevice<Word> for RetryDevice<DEV, F>
+where
+    DEV: SpiDevice<Word>,
+    F: FnMut(&[Operation<'_, Word>]) -> bool,
+{
+    fn transaction(&mut self, operations: &mut [Operation<'_, Word>]) -> Result<(), Self::Error> {
+        for _ in 0..self.attempts(operations) {
+            self.device
+                .transaction(operations)
+                .map_err(RetryError::Device)?;
+            if (self.validator)(operations) {
+                return Ok(());
+            }
+        }
+        Err(RetryError::Invalid)
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+impl<Word: Copy + 'static, DEV, F> AsyncSpiDevice<Word> for RetryDevice<DEV, F>
+where
+    DEV: AsyncSpiDevice<Word>,
+    F: FnMut(&[Operation<'_, Word>]) -> bool,
+{
+    async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, Word>],
+    ) -> Result<(), Self::Error> {
+        for _ in 0..self.attempts(operations) {
+            self.device
+                .transaction(operations)
+                .await
+                .map_err(RetryError::Device)?;
+            if (self.validator)(operations) {
+                return Ok(());
+            }
+        }
+        Err(RetryError::Invalid)
+    }
+}
@@ -0,0 +1,197 @@
+use core::cell::RefCell;
+use embedded_hal::digital::{ErrorType, OutputPin, StatefulOutputPin};
+
+/// 74HC595-style shift register driven over GPIO, for use as a chip select expander.
+///
+/// Designs with more SPI devices than free GPIOs can drive the chip selects from the
+/// outputs of one or more daisy-chained shift registers. [`pin`](ShiftRegister::pin) returns an
+/// [`OutputPin`] handle to a single output, which can be passed as `CS` to any of the
+/// [`SpiDevice`](embedded_hal::spi::SpiDevice) implementations in this module.
+///
+/// `N` is the number of chained registers, giving `8 * N` outputs. Output 0 is `Q0` of the
+/// register connected to the MCU. All outputs are set high on creation, so no device is selected.
+///
+/// Sharing is implemented with a `RefCell`, so the pins can only be used within a single thread
+/// (interrupt priority level), as with [`RefCellDevice`](super::RefCellDevice).
+///
+/// # Examples
+///
+/// ```
+/// use core::cell::RefCell;
+/// use embedded_hal_bus::spi::{RefCellDevice, ShiftRegister};
+/// # use core::convert::Infallible;
+/// # use embedded_hal::digital::{self, OutputPin};
+/// # use embedded_hal::spi::{self, SpiBus};
+/// # pub struct Pin;
+/// # impl digital::ErrorType for Pin { type Error = Infallible; }
+/// # impl OutputPin for Pin {
+/// #     fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// #     fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// # }
+/// # pub struct Spi0;
+/// # impl spi::ErrorType for Spi0 { type Error = spi::ErrorKind; }
+/// # impl SpiBus for Spi0 {
+/// #     fn read(&mut self, _: &mut [u8]) -> Result<(), Self::Error> { Ok(()) }
+/// #     fn write(&mut self, _: &[u8]) -> Result<(), Self::Error> { Ok(()) }
+/// #     fn transfer(&mut self, _: &mut [u8], _: &[u8]) -> Result<(), Self::Error> { Ok(()) }
+/// #     fn transfer_in_place(&mut self, _: &mut [u8]) -> Result<(), Self::Error> { Ok(()) }
+/// #     fn flush(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// # }
+/// # let (data, clock, latch, spi) = (Pin, Pin, Pin, Spi0);
+///
+/// let chip_selects = ShiftRegister::<_, _, _>::new(data, clock, latch)?;
+/// let bus = RefCell::new(spi);
+///
+/// let mut adc = RefCellDevice::new_no_delay(&bus, chip_selects.pin(0))?;
+/// let mut dac = RefCellDevice::new_no_delay(&bus, chip_selects.pin(1))?;
+/// # Ok::<(), Infallible>(())
+/// ```
+pub struct ShiftRegister<DATA, CLK, LATCH, const N: usize = 1> {
+    inner: RefCell<Inner<DATA, CLK, LATCH, N>>,
+}
+
+struct Inner<DATA, CLK, LATCH, const N: usize> {
+    data: DATA,
+    clock: CLK,
+    latch: LATCH,
+    state: [u8; N],
+}
+
+impl<DATA, CLK, LATCH, const N: usize> ShiftRegister<DATA, CLK, LATCH, N>
+where
+    DATA: OutputPin,
+    CLK: OutputPin<Error = DATA::Error>,
+    LATCH: OutputPin<Error = DATA::Error>,
+{
+    /// Create a new [`ShiftRegister`] from its serial data (`SER`), shift clock (`SRCLK`)
+    /// and latch (`RCLK`) pins.
+    ///
+    /// This sets all outputs high, and returns an error if that fails.
+    pub fn new(data: DATA, mut clock: CLK, mut latch: LATCH) -> Result<Self, DATA::Error> {
+        clock.set_low()?;
+        latch.set_low()?;
+
+        let mut inner = Inner {
+            data,
+            clock,
+            latch,
+            state: [0xFF; N],
+        };
+        inner.update()?;
+
+        Ok(Self {
+            inner: RefCell::new(inner),
+        })
+    }
+
+    /// Get a handle to output `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not lower than `8 * N`.
+    #[inline]
+    pub fn pin(&self, index: usize) -> ShiftRegisterPin<'_, DATA, CLK, LATCH, N> {
+        assert!(
+            index < 8 * N,
+            "shift register output {} out of range, there are {} outputs",
+            index,
+            8 * N
+        );
+        ShiftRegisterPin {
+            register: self,
+            index,
+        }
+    }
+
+    /// Destroy the [`ShiftRegister`], returning the data, clock and latch pins.
+    #[inline]
+    pub fn into_inner(self) -> (DATA, CLK, LATCH) {
+        let inner = self.inner.into_inner();
+        (inner.data, inner.clock, inner.latch)
+    }
+
+    fn set(&self, index: usize, high: bool) -> Result<(), DATA::Error> {
+        let inner = &mut *self.inner.borrow_mut();
+        let mask = 1 << (index % 8);
+        if high {
+            inner.state[index / 8] |= mask;
+        } else {
+            inner.state[index / 8] &= !mask;
+        }
+        inner.update()
+    }
+
+    fn is_set_high(&self, index: usize) -> bool {
+        self.inner.borrow().state[index / 8] & (1 << (index % 8)) != 0
+    }
+}
+
+impl<DATA, CLK, LATCH, const N: usize> Inner<DATA, CLK, LATCH, N>
+where
+    DATA: OutputPin,
+    CLK: OutputPin<Error = DATA::Error>,
+    LATCH: OutputPin<Error = DATA::Error>,
+{
+    /// Shift out the whole state, last register and most significant bit first, then latch it.
+    fn update(&mut self) -> Result<(), DATA::Error> {
+        for byte in self.state.iter().rev() {
+            for bit in (0..8).rev() {
+                self.data.set_state((byte & (1 << bit) != 0).into())?;
+                self.clock.set_high()?;
+                self.clock.set_low()?;
+            }
+        }
+        self.latch.set_high()?;
+        self.latch.set_low()
+    }
+}
+
+/// [`OutputPin`] handle to one output of a [`ShiftRegister`].
+///
+/// Changing the pin state shifts out the state of all outputs.
+pub struct ShiftRegisterPin<'a, DATA, CLK, LATCH, const N: usize> {
+    register: &'a ShiftRegister<DATA, CLK, LATCH, N>,
+    index: usize,
+}
+
+impl<'a, DATA, CLK, LATCH, const N: usize> ErrorType for ShiftRegisterPin<'a, DATA, CLK, LATCH, N>
+where
+    DATA: OutputPin,
+{
+    type Error = DATA::Error;
+}
+
+impl<'a, DATA, CLK, LATCH, const N: usize> OutputPin for ShiftRegisterPin<'a, DATA, CLK, LATCH, N>
+where
+    DATA: OutputPin,
+    CLK: OutputPin<Error = DATA::Error>,
+    LATCH: OutputPin<Error = DATA::Error>,
+{
+    #[inline]
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.register.set(self.index, false)
+    }
+
+    #[inline]
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.register.set(self.index, true)
+    }
+}
+
+impl<'a, DATA, CLK, LATCH, const N: usize> StatefulOutputPin
+    for ShiftRegisterPin<'a, DATA, CLK, LATCH, N>
+where
+    DATA: OutputPin,
+    CLK: OutputPin<Error = DATA::Error>,
+    LATCH: OutputPin<Error = DATA::Error>,
+{
+    #[inline]
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.register.is_set_high(self.index))
+    }
+
+    #[inline]
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.register.is_set_high(self.index))
+    }
+}
@@ -2,6 +2,10 @@
 
 #[allow(unused_imports)]
 use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType, OutputPin, StatefulOutputPin};
 
 #[cfg(not(feature = "portable-atomic"))]
 use core::sync::atomic::AtomicBool;
@@ -32,3 +36,294 @@ impl<BUS> AtomicCell<BUS> {
         }
     }
 }
+
+/// [`OutputPin`] wrapper that waits for a settle time after every level change.
+///
+/// Pins that aren't driven directly by the MCU, e.g. the outputs of an I2C GPIO expander or of a
+/// [`ShiftRegister`](crate::spi::ShiftRegister), can take a while to reach their new level, or
+/// have their new level delayed by buffering. Wrapping such a pin in a `SlowPin` before passing it
+/// as `CS` to an [`SpiDevice`](embedded_hal::spi::SpiDevice) implementation, or as reset pin to a
+/// driver, ensures the device sees the new level before the next bus operation.
+pub struct SlowPin<P, D> {
+    pin: P,
+    delay: D,
+    settle_ns: u32,
+}
+
+impl<P, D> SlowPin<P, D> {
+    /// Create a new [`SlowPin`], waiting `settle_ns` nanoseconds after every level change.
+    #[inline]
+    pub fn new(pin: P, delay: D, settle_ns: u32) -> Self {
+        Self {
+            pin,
+            delay,
+            settle_ns,
+        }
+    }
+
+    /// Destroy the [`SlowPin`], returning the wrapped pin and the delay.
+    #[inline]
+    pub fn into_inner(self) -> (P, D) {
+        (self.pin, self.delay)
+    }
+}
+
+impl<P: ErrorType, D> ErrorType for SlowPin<P, D> {
+    type Error = P::Error;
+}
+
+impl<P: OutputPin, D: DelayNs> OutputPin for SlowPin<P, D> {
+    #[inline]
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.pin.set_low()?;
+        self.delay.delay_ns(self.settle_ns);
+        Ok(())
+    }
+
+    #[inline]
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.pin.set_high()?;
+        self.delay.delay_ns(self.settle_ns);
+        Ok(())
+    }
+}
+
+impl<P: StatefulOutputPin, D: DelayNs> StatefulOutputPin for SlowPin<P, D> {
+    #[inline]
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        self.pin.is_set_high()
+    }
+
+    #[inline]
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        self.pin.is_set_low()
+    }
+}
+
+/// Fixed-capacity list of transaction operations, for building transactions without an allocator.
+///
+/// An `OpList` stores up to `N` operations inline. It dereferences to a slice, so it can be passed
+/// directly to [`I2c::transaction`](embedded_hal::i2c::I2c::transaction),
+/// [`SpiDevice::transaction`](embedded_hal::spi::SpiDevice::transaction) and the like.
+/// This allows building a transaction step by step, e.g. across helper functions, without
+/// juggling slices of operations.
+///
+/// ```
+/// use embedded_hal::i2c::{I2c, Operation};
+/// use embedded_hal_bus::util::OpList;
+/// # use embedded_hal::i2c::{ErrorKind, ErrorType};
+/// # pub struct I2c0;
+/// # impl ErrorType for I2c0 { type Error = ErrorKind; }
+/// # impl I2c for I2c0 {
+/// #     fn transaction(&mut self, _: u8, _: &mut [Operation<'_>]) -> Result<(), Self::Error> { Ok(()) }
+/// # }
+/// # let mut i2c = I2c0;
+///
+/// let mut status = [0; 1];
+/// let mut data = [0; 6];
+///
+/// let mut ops = OpList::<_, 4>::new();
+/// ops.push(Operation::Write(&[0x00])).unwrap();
+/// ops.push(Operation::Read(&mut status)).unwrap();
+/// ops.push(Operation::Read(&mut data)).unwrap();
+/// i2c.transaction(0x76, &mut ops)?;
+///
+/// // Lists can also be created from arrays.
+/// let mut ops = OpList::from([Operation::Write(&[0xF4, 0x27])]);
+/// i2c.transaction(0x76, &mut ops)?;
+/// # Ok::<(), ErrorKind>(())
+/// ```
+pub struct OpList<T, const N: usize> {
+    ops: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> OpList<T, N> {
+    /// Create a new, empty [`OpList`].
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            ops: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    /// Append an operation to the list.
+    ///
+    /// Returns the operation back if the list is full.
+    #[inline]
+    pub fn push(&mut self, op: T) -> Result<(), T> {
+        if self.len == N {
+            return Err(op);
+        }
+        self.ops[self.len].write(op);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Remove the last operation from the list and return it, or `None` if the list is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: elements below the previous `len` are initialized, and this one is no longer
+        // considered part of the list.
+        Some(unsafe { self.ops[self.len].assume_init_read() })
+    }
+
+    /// Remove all operations from the list.
+    #[inline]
+    pub fn clear(&mut self) {
+        let ops: *mut [T] = self.deref_mut();
+        self.len = 0;
+        // SAFETY: the elements were initialized, and are no longer part of the list.
+        unsafe { core::ptr::drop_in_place(ops) };
+    }
+
+    /// Maximum number of operations the list can hold.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the list holds [`capacity`](OpList::capacity) operations.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+}
+
+impl<T, const N: usize> Default for OpList<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for OpList<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { core::slice::from_raw_parts(self.ops.as_ptr().cast(), self.len) }
+    }
+}
+
+impl<T, const N: usize> DerefMut for OpList<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { core::slice::from_raw_parts_mut(self.ops.as_mut_ptr().cast(), self.len) }
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for OpList<T, N> {
+    #[inline]
+    fn from(ops: [T; N]) -> Self {
+        Self {
+            ops: ops.map(MaybeUninit::new),
+            len: N,
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for OpList<T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    /// Counts how many times it has been dropped.
+    struct Counted<'a>(&'a Cell<usize>);
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn push_to_full() {
+        let mut ops = OpList::<u8, 2>::new();
+        assert_eq!(ops.capacity(), 2);
+        assert_eq!(ops.push(1), Ok(()));
+        assert!(!ops.is_full());
+        assert_eq!(ops.push(2), Ok(()));
+        assert!(ops.is_full());
+        assert_eq!(ops.push(3), Err(3));
+        assert_eq!(&ops[..], &[1, 2]);
+    }
+
+    #[test]
+    fn pop_to_empty() {
+        let mut ops = OpList::<u8, 2>::from([1, 2]);
+        assert_eq!(ops.pop(), Some(2));
+        assert_eq!(ops.pop(), Some(1));
+        assert_eq!(ops.pop(), None);
+        assert!(ops.is_empty());
+
+        // The freed slots can be reused.
+        assert_eq!(ops.push(3), Ok(()));
+        assert_eq!(&ops[..], &[3]);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut ops = OpList::<u8, 0>::new();
+        assert!(ops.is_full());
+        assert_eq!(ops.push(1), Err(1));
+        assert_eq!(ops.pop(), None);
+    }
+
+    #[test]
+    fn clear_drops_elements() {
+        let drops = Cell::new(0);
+        let mut ops = OpList::<_, 4>::new();
+        for _ in 0..3 {
+            assert!(ops.push(Counted(&drops)).is_ok());
+        }
+
+        ops.clear();
+        assert_eq!(drops.get(), 3);
+        assert!(ops.is_empty());
+
+        // Nothing is dropped twice.
+        drop(ops);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn drop_drops_elements() {
+        let drops = Cell::new(0);
+        let mut ops = OpList::<_, 4>::new();
+        for _ in 0..3 {
+            assert!(ops.push(Counted(&drops)).is_ok());
+        }
+        drop(ops.pop());
+        assert_eq!(drops.get(), 1);
+
+        drop(ops);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn from_array_then_drop() {
+        let drops = Cell::new(0);
+        let ops = OpList::from([Counted(&drops), Counted(&drops), Counted(&drops)]);
+        assert!(ops.is_full());
+        assert_eq!(ops.len(), 3);
+        assert_eq!(drops.get(), 0);
+
+        drop(ops);
+        assert_eq!(drops.get(), 3);
+    }
+}
@@ -0,0 +1,30 @@
+//! Delays using `std::thread::sleep`.
+
+use embedded_hal::delay::DelayNs;
+use std::thread;
+use std::time::Duration;
+
+/// [`DelayNs`] implementation that puts the current thread to sleep.
+///
+/// The OS scheduler makes no guarantees on how soon the thread is woken up again, so
+/// delays are usually much longer than requested for small values. This is allowed by the
+/// [`DelayNs`] contract, but drivers relying on tight timing won't work reliably.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Delay;
+
+impl DelayNs for Delay {
+    #[inline]
+    fn delay_ns(&mut self, ns: u32) {
+        thread::sleep(Duration::from_nanos(ns.into()));
+    }
+
+    #[inline]
+    fn delay_us(&mut self, us: u32) {
+        thread::sleep(Duration::from_micros(us.into()));
+    }
+
+    #[inline]
+    fn delay_ms(&mut self, ms: u32) {
+        thread::sleep(Duration::from_millis(ms.into()));
+    }
+}
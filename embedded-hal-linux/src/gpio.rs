@@ -0,0 +1,116 @@
+//! GPIO pins using the Linux GPIO character device.
+
+use core::fmt;
+use embedded_hal::digital::{self, ErrorType, InputPin, OutputPin, StatefulOutputPin};
+use gpio_cdev::LineHandle;
+
+/// GPIO pin backed by a line handle requested from a `/dev/gpiochipN` character device.
+///
+/// The direction, bias and polarity of the pin are configured when requesting the line
+/// handle. Active-low lines are handled by the kernel, so `set_high` always means "active".
+///
+/// # Examples
+///
+/// ```no_run
+/// use embedded_hal::digital::OutputPin;
+/// use embedded_hal_linux::gpio::CdevPin;
+/// use gpio_cdev::{Chip, LineRequestFlags};
+///
+/// let mut chip = Chip::new("/dev/gpiochip0")?;
+/// let handle = chip
+///     .get_line(17)?
+///     .request(LineRequestFlags::OUTPUT, 0, "my-driver")?;
+/// let mut led = CdevPin::new(handle);
+/// led.set_high()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct CdevPin {
+    handle: LineHandle,
+}
+
+impl CdevPin {
+    /// Create a new [`CdevPin`] from a requested line handle.
+    #[inline]
+    pub fn new(handle: LineHandle) -> Self {
+        Self { handle }
+    }
+
+    /// Consume the pin, returning the line handle.
+    #[inline]
+    pub fn into_inner(self) -> LineHandle {
+        self.handle
+    }
+
+    #[inline]
+    fn get(&self) -> Result<bool, CdevPinError> {
+        Ok(self.handle.get_value().map_err(CdevPinError)? != 0)
+    }
+
+    #[inline]
+    fn set(&self, value: bool) -> Result<(), CdevPinError> {
+        self.handle.set_value(value.into()).map_err(CdevPinError)
+    }
+}
+
+/// Error type for [`CdevPin`] operations.
+#[derive(Debug)]
+pub struct CdevPinError(pub gpio_cdev::Error);
+
+impl fmt::Display for CdevPinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GPIO character device error: {}", self.0)
+    }
+}
+
+impl std::error::Error for CdevPinError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl digital::Error for CdevPinError {
+    #[inline]
+    fn kind(&self) -> digital::ErrorKind {
+        digital::ErrorKind::Other
+    }
+}
+
+impl ErrorType for CdevPin {
+    type Error = CdevPinError;
+}
+
+impl InputPin for CdevPin {
+    #[inline]
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.get().map(|high| !high)
+    }
+}
+
+impl OutputPin for CdevPin {
+    #[inline]
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set(false)
+    }
+
+    #[inline]
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set(true)
+    }
+}
+
+impl StatefulOutputPin for CdevPin {
+    #[inline]
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        self.get().map(|high| !high)
+    }
+}
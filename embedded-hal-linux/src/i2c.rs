@@ -0,0 +1,254 @@
+//! I2C buses using the Linux i2c-dev interface.
+
+use core::fmt;
+use embedded_hal::i2c::{
+    self, ErrorType, I2c, NoAcknowledgeSource, Operation, SevenBitAddress, TenBitAddress,
+};
+use i2cdev::core::{I2CMessage, I2CTransfer};
+use i2cdev::linux::{I2CMessageFlags, LinuxI2CBus, LinuxI2CError, LinuxI2CMessage};
+use std::ops::Range;
+use std::path::Path;
+
+/// I2C bus backed by a `/dev/i2c-N` device.
+///
+/// Transactions are executed with a single `I2C_RDWR` ioctl. Adjacent operations of the same
+/// type are merged into a single message, so the kernel only sends repeated starts between a
+/// write and a read, as required by the [`I2c::transaction`] contract. Merging copies the
+/// buffers of these operations.
+///
+/// # Examples
+///
+/// ```no_run
+/// use embedded_hal::i2c::I2c;
+/// use embedded_hal_linux::i2c::I2cdev;
+///
+/// let mut i2c = I2cdev::new("/dev/i2c-1")?;
+/// let mut id = [0];
+/// i2c.write_read(0x76u8, &[0xD0], &mut id)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct I2cdev {
+    bus: LinuxI2CBus,
+}
+
+impl I2cdev {
+    /// Open the I2C bus at `path`, such as `/dev/i2c-1`.
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, I2cError> {
+        Ok(Self {
+            bus: LinuxI2CBus::new(path).map_err(I2cError)?,
+        })
+    }
+
+    /// Consume the bus, returning the underlying i2c-dev handle.
+    #[inline]
+    pub fn into_inner(self) -> LinuxI2CBus {
+        self.bus
+    }
+
+    fn transaction_impl(
+        &mut self,
+        address: u16,
+        flags: I2CMessageFlags,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), I2cError> {
+        // Adjacent operations of the same type must not be separated by a repeated start, so
+        // each run of them is sent as a single message, merged into a scratch buffer.
+        let runs = runs(operations);
+        let mut scratch: Vec<Vec<u8>> = runs
+            .iter()
+            .map(|(run, _)| {
+                if run.len() > 1 {
+                    merge(&operations[run.clone()])
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect();
+
+        {
+            let mut ops = operations.iter_mut();
+            let mut messages: Vec<LinuxI2CMessage<'_>> = runs
+                .iter()
+                .zip(&mut scratch)
+                .map(|((run, read), buf)| {
+                    let (msg, direction) = if run.len() > 1 {
+                        ops.nth(run.len() - 1);
+                        if *read {
+                            (LinuxI2CMessage::read(buf), I2CMessageFlags::READ)
+                        } else {
+                            (LinuxI2CMessage::write(buf), I2CMessageFlags::empty())
+                        }
+                    } else {
+                        match ops.next().unwrap() {
+                            Operation::Read(buf) => {
+                                (LinuxI2CMessage::read(buf), I2CMessageFlags::READ)
+                            }
+                            Operation::Write(buf) => {
+                                (LinuxI2CMessage::write(buf), I2CMessageFlags::empty())
+                            }
+                        }
+                    };
+                    msg.with_address(address)
+                        .with_flags(I2CMessageFlags::from_bits_truncate(
+                            flags.bits() | direction.bits(),
+                        ))
+                })
+                .collect();
+
+            self.bus.transfer(&mut messages).map_err(I2cError)?;
+        }
+
+        // Copy merged reads back into the operations' buffers.
+        for ((run, read), buf) in runs.into_iter().zip(scratch) {
+            if read && run.len() > 1 {
+                scatter(&buf, &mut operations[run]);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Split `operations` into runs of adjacent operations of the same type.
+///
+/// Returns the range of each run in `operations`, and whether it is a run of reads.
+fn runs(operations: &[Operation<'_>]) -> Vec<(Range<usize>, bool)> {
+    let mut runs: Vec<(Range<usize>, bool)> = Vec::new();
+    for (i, op) in operations.iter().enumerate() {
+        let read = matches!(op, Operation::Read(_));
+        match runs.last_mut() {
+            Some((run, r)) if *r == read => run.end = i + 1,
+            _ => runs.push((i..i + 1, read)),
+        }
+    }
+    runs
+}
+
+/// Merge a run of operations into a single buffer: the concatenated data of writes, or zeroes
+/// for the total length of reads.
+fn merge(run: &[Operation<'_>]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for op in run {
+        match op {
+            Operation::Read(b) => buf.resize(buf.len() + b.len(), 0),
+            Operation::Write(b) => buf.extend_from_slice(b),
+        }
+    }
+    buf
+}
+
+/// Copy a merged read buffer back into the buffers of the reads in `run`.
+fn scatter(merged: &[u8], run: &mut [Operation<'_>]) {
+    let mut rest = merged;
+    for op in run {
+        if let Operation::Read(b) = op {
+            let (head, tail) = rest.split_at(b.len());
+            b.copy_from_slice(head);
+            rest = tail;
+        }
+    }
+}
+
+/// Error type for [`I2cdev`] operations.
+#[derive(Debug)]
+pub struct I2cError(pub LinuxI2CError);
+
+impl fmt::Display for I2cError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "I2C error: {}", self.0)
+    }
+}
+
+impl std::error::Error for I2cError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl i2c::Error for I2cError {
+    fn kind(&self) -> i2c::ErrorKind {
+        // See https://www.kernel.org/doc/html/latest/i2c/fault-codes.html
+        match self.0 {
+            LinuxI2CError::Errno(libc::ENXIO) | LinuxI2CError::Errno(libc::EREMOTEIO) => {
+                i2c::ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown)
+            }
+            LinuxI2CError::Errno(libc::EAGAIN) => i2c::ErrorKind::ArbitrationLoss,
+            LinuxI2CError::Errno(libc::EPROTO) => i2c::ErrorKind::Bus,
+            _ => i2c::ErrorKind::Other,
+        }
+    }
+}
+
+impl ErrorType for I2cdev {
+    type Error = I2cError;
+}
+
+impl I2c<SevenBitAddress> for I2cdev {
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.transaction_impl(address.into(), I2CMessageFlags::empty(), operations)
+    }
+}
+
+impl I2c<TenBitAddress> for I2cdev {
+    fn transaction(
+        &mut self,
+        address: TenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.transaction_impl(address, I2CMessageFlags::TEN_BIT_ADDRESS, operations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_of_same_type() {
+        let (mut r1, mut r2) = ([0; 1], [0; 2]);
+        let ops = [
+            Operation::Write(&[1]),
+            Operation::Write(&[2, 3]),
+            Operation::Read(&mut r1),
+            Operation::Read(&mut r2),
+            Operation::Write(&[4]),
+        ];
+        assert_eq!(runs(&ops), [(0..2, false), (2..4, true), (4..5, false)]);
+    }
+
+    #[test]
+    fn runs_alternating() {
+        let mut r = [0; 1];
+        let ops = [Operation::Write(&[1]), Operation::Read(&mut r)];
+        assert_eq!(runs(&ops), [(0..1, false), (1..2, true)]);
+        assert!(runs(&[]).is_empty());
+    }
+
+    #[test]
+    fn merge_writes() {
+        let ops = [
+            Operation::Write(&[1]),
+            Operation::Write(&[]),
+            Operation::Write(&[2, 3]),
+        ];
+        assert_eq!(merge(&ops), [1, 2, 3]);
+    }
+
+    #[test]
+    fn merge_then_scatter_reads() {
+        let (mut r1, mut r2, mut r3) = ([0xFF; 2], [0xFF; 0], [0xFF; 3]);
+        let mut ops = [
+            Operation::Read(&mut r1),
+            Operation::Read(&mut r2),
+            Operation::Read(&mut r3),
+        ];
+        assert_eq!(merge(&ops), [0; 5]);
+
+        scatter(&[1, 2, 3, 4, 5], &mut ops);
+        assert_eq!(r1, [1, 2]);
+        assert_eq!(r3, [3, 4, 5]);
+    }
+}
@@ -0,0 +1,36 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+#![cfg_attr(not(target_os = "linux"), no_std)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+#[cfg(target_os = "linux")]
+pub mod delay;
+
+#[cfg(all(target_os = "linux", feature = "gpio-cdev"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "gpio-cdev")))]
+pub mod gpio;
+
+#[cfg(all(target_os = "linux", feature = "i2cdev"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "i2cdev")))]
+pub mod i2c;
+
+#[cfg(all(target_os = "linux", feature = "spidev"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "spidev")))]
+pub mod spi;
+
+#[cfg(all(target_os = "linux", feature = "serialport"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "serialport")))]
+pub mod serial;
+
+#[cfg(all(target_os = "linux", feature = "gpio-cdev"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "gpio-cdev")))]
+pub use gpio_cdev;
+#[cfg(all(target_os = "linux", feature = "i2cdev"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "i2cdev")))]
+pub use i2cdev;
+#[cfg(all(target_os = "linux", feature = "serialport"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "serialport")))]
+pub use serialport;
+#[cfg(all(target_os = "linux", feature = "spidev"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "spidev")))]
+pub use spidev;
@@ -0,0 +1,147 @@
+//! Serial ports.
+//!
+//! [`Serial`] implements the [`embedded-io`](embedded_io) traits, which are the recommended way of
+//! using serial ports, as well as the word-at-a-time [`embedded-hal-nb`](embedded_hal_nb::serial) traits.
+
+use core::fmt;
+use embedded_hal_nb::{nb, serial};
+use serialport::SerialPort;
+use std::io::{self, Read as _, Write as _};
+use std::time::Duration;
+
+/// Serial port backed by a TTY device, such as `/dev/ttyUSB0` or `/dev/ttyAMA0`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use embedded_io::Write;
+/// use embedded_hal_linux::serial::Serial;
+///
+/// let mut serial = Serial::open("/dev/ttyUSB0", 115_200)?;
+/// serial.write_all(b"hello\r\n")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct Serial {
+    port: Box<dyn SerialPort>,
+}
+
+impl Serial {
+    /// Open the serial port at `path` with the given baud rate, using 8N1 framing.
+    pub fn open(path: &str, baud_rate: u32) -> Result<Self, SerialError> {
+        let port = serialport::new(path, baud_rate)
+            .timeout(Duration::from_secs(1))
+            .open()
+            .map_err(|e| SerialError(e.into()))?;
+        Ok(Self { port })
+    }
+
+    /// Create a new [`Serial`] from a port opened with custom settings.
+    #[inline]
+    pub fn new(port: Box<dyn SerialPort>) -> Self {
+        Self { port }
+    }
+
+    /// Consume the wrapper, returning the underlying serial port.
+    #[inline]
+    pub fn into_inner(self) -> Box<dyn SerialPort> {
+        self.port
+    }
+}
+
+/// Error type for [`Serial`] operations.
+#[derive(Debug)]
+pub struct SerialError(pub io::Error);
+
+impl fmt::Display for SerialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "serial port error: {}", self.0)
+    }
+}
+
+impl std::error::Error for SerialError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl embedded_io::Error for SerialError {
+    #[inline]
+    fn kind(&self) -> embedded_io::ErrorKind {
+        self.0.kind().into()
+    }
+}
+
+impl serial::Error for SerialError {
+    #[inline]
+    fn kind(&self) -> serial::ErrorKind {
+        serial::ErrorKind::Other
+    }
+}
+
+impl embedded_io::ErrorType for Serial {
+    type Error = SerialError;
+}
+
+impl embedded_io::Read for Serial {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // `embedded_io::Read` blocks until at least one byte is available, it doesn't time out.
+        loop {
+            match self.port.read(buf) {
+                Err(e) if e.kind() == io::ErrorKind::TimedOut => {}
+                res => return res.map_err(SerialError),
+            }
+        }
+    }
+}
+
+impl embedded_io::ReadReady for Serial {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        let available = self
+            .port
+            .bytes_to_read()
+            .map_err(|e| SerialError(e.into()))?;
+        Ok(available != 0)
+    }
+}
+
+impl embedded_io::Write for Serial {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.port.write(buf).map_err(SerialError)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.port.flush().map_err(SerialError)
+    }
+}
+
+impl serial::ErrorType for Serial {
+    type Error = SerialError;
+}
+
+impl serial::Read for Serial {
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        if !embedded_io::ReadReady::read_ready(self)? {
+            return Err(nb::Error::WouldBlock);
+        }
+        let mut buf = [0];
+        match embedded_io::Read::read(self, &mut buf)? {
+            0 => Err(nb::Error::WouldBlock),
+            _ => Ok(buf[0]),
+        }
+    }
+}
+
+impl serial::Write for Serial {
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.port.write_all(&[word]).map_err(SerialError)?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.port.flush().map_err(SerialError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+//! SPI buses and devices using the Linux spidev interface.
+//!
+//! Linux manages the CS pin of a `/dev/spidevB.C` device itself, asserting it for the
+//! duration of each `SPI_IOC_MESSAGE` ioctl. This gives two ways of using it:
+//!
+//! - [`SpidevDevice`] implements [`SpiDevice`], letting the kernel drive CS. Each
+//!   transaction is executed as a single ioctl, so CS stays asserted for all of its operations.
+//! - [`SpidevBus`] implements [`SpiBus`]. Each method call is a separate ioctl, so the device
+//!   should be configured with [`SpiModeFlags::SPI_NO_CS`] and combined with a GPIO CS pin
+//!   using one of the [`embedded-hal-bus`](https://docs.rs/embedded-hal-bus) devices.
+
+use core::fmt;
+use embedded_hal::spi::{self, ErrorType, Operation, SpiBus, SpiDevice};
+use spidev::{Spidev, SpidevOptions, SpidevTransfer};
+use std::io;
+use std::path::Path;
+
+#[cfg(doc)]
+use spidev::SpiModeFlags;
+
+/// SPI bus backed by a `/dev/spidevB.C` device.
+///
+/// See the [module-level documentation](self) for details on how CS is handled.
+pub struct SpidevBus(Spidev);
+
+/// SPI device backed by a `/dev/spidevB.C` device, with CS managed by the kernel.
+///
+/// See the [module-level documentation](self) for details on how CS is handled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use embedded_hal::spi::SpiDevice;
+/// use embedded_hal_linux::spi::SpidevDevice;
+/// use spidev::{SpiModeFlags, SpidevOptions};
+///
+/// let mut spi = SpidevDevice::open("/dev/spidev0.0")?;
+/// spi.configure(
+///     &SpidevOptions::new()
+///         .max_speed_hz(1_000_000)
+///         .mode(SpiModeFlags::SPI_MODE_0)
+///         .build(),
+/// )?;
+/// let mut id = [0; 3];
+/// spi.transfer(&mut id, &[0x9F])?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct SpidevDevice(Spidev);
+
+impl SpidevBus {
+    /// Open the spidev device at `path`, such as `/dev/spidev0.0`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SpidevError> {
+        Ok(Self(Spidev::open(path).map_err(SpidevError)?))
+    }
+
+    /// Configure the mode, speed and word size of the device.
+    #[inline]
+    pub fn configure(&mut self, options: &SpidevOptions) -> Result<(), SpidevError> {
+        self.0.configure(options).map_err(SpidevError)
+    }
+
+    /// Consume the wrapper, returning the underlying spidev handle.
+    #[inline]
+    pub fn into_inner(self) -> Spidev {
+        self.0
+    }
+
+    #[inline]
+    fn run(&mut self, transfers: &mut [SpidevTransfer<'_, '_>]) -> Result<(), SpidevError> {
+        self.0.transfer_multiple(transfers).map_err(SpidevError)
+    }
+}
+
+impl From<Spidev> for SpidevBus {
+    #[inline]
+    fn from(spidev: Spidev) -> Self {
+        Self(spidev)
+    }
+}
+
+impl ErrorType for SpidevBus {
+    type Error = SpidevError;
+}
+
+impl SpidevDevice {
+    /// Open the spidev device at `path`, such as `/dev/spidev0.0`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SpidevError> {
+        Ok(Self(Spidev::open(path).map_err(SpidevError)?))
+    }
+
+    /// Configure the mode, speed and word size of the device.
+    #[inline]
+    pub fn configure(&mut self, options: &SpidevOptions) -> Result<(), SpidevError> {
+        self.0.configure(options).map_err(SpidevError)
+    }
+
+    /// Consume the wrapper, returning the underlying spidev handle.
+    #[inline]
+    pub fn into_inner(self) -> Spidev {
+        self.0
+    }
+
+    #[inline]
+    fn run(&mut self, transfers: &mut [SpidevTransfer<'_, '_>]) -> Result<(), SpidevError> {
+        self.0.transfer_multiple(transfers).map_err(SpidevError)
+    }
+}
+
+impl From<Spidev> for SpidevDevice {
+    #[inline]
+    fn from(spidev: Spidev) -> Self {
+        Self(spidev)
+    }
+}
+
+impl ErrorType for SpidevDevice {
+    type Error = SpidevError;
+}
+
+/// Push the transfers needed for a full-duplex transfer where `read` and `write` may differ in length.
+fn push_transfer<'a>(
+    transfers: &mut Vec<SpidevTransfer<'a, 'a>>,
+    read: &'a mut [u8],
+    write: &'a [u8],
+) {
+    let common = read.len().min(write.len());
+    let (read, read_rest) = read.split_at_mut(common);
+    let (write, write_rest) = write.split_at(common);
+
+    if common != 0 {
+        transfers.push(SpidevTransfer::read_write(write, read));
+    }
+    if !read_rest.is_empty() {
+        transfers.push(SpidevTransfer::read(read_rest));
+    }
+    if !write_rest.is_empty() {
+        transfers.push(SpidevTransfer::write(write_rest));
+    }
+}
+
+impl SpiBus for SpidevBus {
+    fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        self.run(&mut [SpidevTransfer::read(words)])
+    }
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        self.run(&mut [SpidevTransfer::write(words)])
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        let mut transfers = Vec::with_capacity(2);
+        push_transfer(&mut transfers, read, write);
+        self.run(&mut transfers)
+    }
+
+    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        self.run(&mut [SpidevTransfer::read_write_in_place(words)])
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        // Every ioctl completes before returning.
+        Ok(())
+    }
+}
+
+impl SpiDevice for SpidevDevice {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        let mut transfers = Vec::with_capacity(operations.len());
+        for op in operations {
+            match op {
+                Operation::Read(buf) => transfers.push(SpidevTransfer::read(buf)),
+                Operation::Write(buf) => transfers.push(SpidevTransfer::write(buf)),
+                Operation::Transfer(read, write) => push_transfer(&mut transfers, read, write),
+                Operation::TransferInPlace(buf) => {
+                    transfers.push(SpidevTransfer::read_write_in_place(buf))
+                }
+                Operation::DelayNs(ns) => {
+                    // spidev delays have microsecond resolution and are limited to `u16::MAX`.
+                    let mut us = ns.div_ceil(1_000);
+                    while us > 0 {
+                        let chunk = us.min(u16::MAX.into());
+                        transfers.push(SpidevTransfer::delay(chunk as u16));
+                        us -= chunk;
+                    }
+                }
+            }
+        }
+        self.run(&mut transfers)
+    }
+}
+
+/// Error type for [`SpidevBus`] and [`SpidevDevice`] operations.
+#[derive(Debug)]
+pub struct SpidevError(pub io::Error);
+
+impl fmt::Display for SpidevError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "spidev error: {}", self.0)
+    }
+}
+
+impl std::error::Error for SpidevError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl spi::Error for SpidevError {
+    #[inline]
+    fn kind(&self) -> spi::ErrorKind {
+        spi::ErrorKind::Other
+    }
+}
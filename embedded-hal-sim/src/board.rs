@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::{delay, digital, i2c, spi};
+use embedded_hal::i2c::{SevenBitAddress, TenBitAddress};
+
+/// Virtual board.
+///
+/// Cloning a `Board` gives another handle to the same simulated hardware.
+/// See the [crate-level documentation](crate) for an overview.
+#[derive(Clone, Default)]
+pub struct Board {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    now: Duration,
+    pins: BTreeMap<u32, bool>,
+    i2c: BTreeMap<i2c::Address, Arc<Mutex<dyn i2c::Model>>>,
+}
+
+impl Board {
+    /// Create a new board, with the clock at zero, all pins low and no devices attached.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Virtual time elapsed since the board was created.
+    pub fn now(&self) -> Duration {
+        self.lock().now
+    }
+
+    /// Advance the virtual clock by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        self.lock().now += duration;
+    }
+
+    /// Get a [`DelayNs`](embedded_hal::delay::DelayNs) implementation that advances the virtual clock.
+    #[inline]
+    pub fn delay(&self) -> delay::Delay {
+        delay::Delay::new(self.clone())
+    }
+
+    /// Get a handle to pin `id`.
+    ///
+    /// Several handles to the same pin can exist at once, they all see the same level.
+    #[inline]
+    pub fn pin(&self, id: u32) -> digital::Pin {
+        digital::Pin::new(self.clone(), id)
+    }
+
+    /// Drive pin `id` from outside, e.g. to simulate a button press or an interrupt line.
+    pub fn set_pin(&self, id: u32, high: bool) {
+        self.lock().pins.insert(id, high);
+    }
+
+    /// Returns `true` if pin `id` is currently high.
+    pub fn is_pin_high(&self, id: u32) -> bool {
+        self.lock().pins.get(&id).copied().unwrap_or(false)
+    }
+
+    /// Get a handle to the I2C bus.
+    #[inline]
+    pub fn i2c(&self) -> i2c::Bus {
+        i2c::Bus::new(self.clone())
+    }
+
+    /// Attach an I2C device model at 7-bit `address`, replacing any model already there.
+    pub fn attach_i2c(&self, address: SevenBitAddress, model: impl i2c::Model + 'static) {
+        self.attach_i2c_impl(i2c::Address::SevenBit(address), model);
+    }
+
+    /// Attach an I2C device model at 10-bit `address`, replacing any model already there.
+    pub fn attach_i2c_ten_bit(&self, address: TenBitAddress, model: impl i2c::Model + 'static) {
+        self.attach_i2c_impl(i2c::Address::TenBit(address), model);
+    }
+
+    /// Detach the I2C device model at 7-bit `address`, e.g. to simulate unplugging it.
+    pub fn detach_i2c(&self, address: SevenBitAddress) {
+        self.lock().i2c.remove(&i2c::Address::SevenBit(address));
+    }
+
+    /// Detach the I2C device model at 10-bit `address`, e.g. to simulate unplugging it.
+    pub fn detach_i2c_ten_bit(&self, address: TenBitAddress) {
+        self.lock().i2c.remove(&i2c::Address::TenBit(address));
+    }
+
+    fn attach_i2c_impl(&self, address: i2c::Address, model: impl i2c::Model + 'static) {
+        self.lock().i2c.insert(address, Arc::new(Mutex::new(model)));
+    }
+
+    /// Attach an SPI device model, returning the [`SpiDevice`](embedded_hal::spi::SpiDevice) to talk to it.
+    pub fn attach_spi(&self, model: impl spi::Model + 'static) -> spi::Device {
+        spi::Device::new(self.clone(), Arc::new(Mutex::new(model)))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+
+    /// Get the I2C model at `address`, if any.
+    ///
+    /// Models are called without holding the board state lock, so they can use the board,
+    /// e.g. to raise an interrupt pin.
+    pub(crate) fn i2c_model(&self, address: i2c::Address) -> Option<Arc<Mutex<dyn i2c::Model>>> {
+        self.lock().i2c.get(&address).cloned()
+    }
+}
+
+pub(crate) fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // A model panicking inside a transaction shouldn't break the remaining tests.
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal::i2c::{self, I2c};
+    use embedded_hal::spi::{self, SpiDevice};
+
+    #[test]
+    fn i2c_model_can_use_board() {
+        let board = Board::new();
+        let handle = board.clone();
+        board.attach_i2c(0x48, move |now, _: &mut [i2c::Operation<'_>]| {
+            assert_eq!(handle.now(), now);
+            handle.set_pin(3, true);
+            Ok(())
+        });
+
+        board.i2c().write(0x48u8, &[0]).unwrap();
+        assert!(board.is_pin_high(3));
+    }
+
+    #[test]
+    fn i2c_address_modes_are_distinct() {
+        let board = Board::new();
+        board.attach_i2c(0x48, |_, _: &mut [i2c::Operation<'_>]| Ok(()));
+        board.attach_i2c_ten_bit(0x149, |_, _: &mut [i2c::Operation<'_>]| Ok(()));
+
+        let mut bus = board.i2c();
+        let nack = Err(i2c::ErrorKind::NoAcknowledge(
+            i2c::NoAcknowledgeSource::Address,
+        ));
+        assert_eq!(bus.write(0x48u8, &[0]), Ok(()));
+        assert_eq!(bus.write(0x048u16, &[0]), nack);
+        assert_eq!(bus.write(0x149u16, &[0]), Ok(()));
+
+        board.detach_i2c(0x48);
+        assert_eq!(bus.write(0x48u8, &[0]), nack);
+        board.detach_i2c_ten_bit(0x149);
+        assert_eq!(bus.write(0x149u16, &[0]), nack);
+    }
+
+    #[test]
+    fn spi_model_can_use_board() {
+        let board = Board::new();
+        let handle = board.clone();
+        let mut device = board.attach_spi(move |now, _: &mut [spi::Operation<'_, u8>]| {
+            assert_eq!(handle.now(), now);
+            handle.set_pin(3, true);
+            Ok(())
+        });
+
+        device.write(&[0]).unwrap();
+        assert!(board.is_pin_high(3));
+    }
+}
@@ -0,0 +1,36 @@
+//! Simulated delays.
+
+use crate::Board;
+use embedded_hal::delay::DelayNs;
+use std::time::Duration;
+
+/// [`DelayNs`] implementation that advances the virtual clock of a [`Board`].
+///
+/// Delays return immediately in real time.
+#[derive(Clone)]
+pub struct Delay {
+    board: Board,
+}
+
+impl Delay {
+    pub(crate) fn new(board: Board) -> Self {
+        Self { board }
+    }
+}
+
+impl DelayNs for Delay {
+    #[inline]
+    fn delay_ns(&mut self, ns: u32) {
+        self.board.advance(Duration::from_nanos(ns.into()));
+    }
+
+    #[inline]
+    fn delay_us(&mut self, us: u32) {
+        self.board.advance(Duration::from_micros(us.into()));
+    }
+
+    #[inline]
+    fn delay_ms(&mut self, ms: u32) {
+        self.board.advance(Duration::from_millis(ms.into()));
+    }
+}
@@ -0,0 +1,69 @@
+//! Simulated pins.
+
+use crate::Board;
+use core::convert::Infallible;
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin, StatefulOutputPin};
+
+/// Pin of a [`Board`].
+///
+/// A pin is both an input and an output: reading it returns the level last set either through
+/// any handle to the pin, or from outside with [`Board::set_pin`].
+#[derive(Clone)]
+pub struct Pin {
+    board: Board,
+    id: u32,
+}
+
+impl Pin {
+    pub(crate) fn new(board: Board, id: u32) -> Self {
+        Self { board, id }
+    }
+
+    /// The pin number.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl ErrorType for Pin {
+    type Error = Infallible;
+}
+
+impl InputPin for Pin {
+    #[inline]
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.board.is_pin_high(self.id))
+    }
+
+    #[inline]
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.board.is_pin_high(self.id))
+    }
+}
+
+impl OutputPin for Pin {
+    #[inline]
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.board.set_pin(self.id, false);
+        Ok(())
+    }
+
+    #[inline]
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.board.set_pin(self.id, true);
+        Ok(())
+    }
+}
+
+impl StatefulOutputPin for Pin {
+    #[inline]
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        self.is_high()
+    }
+
+    #[inline]
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_low()
+    }
+}
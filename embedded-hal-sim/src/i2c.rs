@@ -0,0 +1,101 @@
+//! Simulated I2C bus.
+
+use crate::Board;
+use embedded_hal::i2c::{
+    ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation, SevenBitAddress, TenBitAddress,
+};
+use std::time::Duration;
+
+/// Model of an I2C target device.
+///
+/// Implemented for closures with a matching signature, which is often enough for simple devices.
+pub trait Model: Send {
+    /// Handle a transaction addressed to this device.
+    ///
+    /// `now` is the virtual time at the start of the transaction. The model fills the buffers of
+    /// [`Operation::Read`]s, and returns an error to simulate a bus fault.
+    fn transaction(
+        &mut self,
+        now: Duration,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), ErrorKind>;
+}
+
+impl<F> Model for F
+where
+    F: FnMut(Duration, &mut [Operation<'_>]) -> Result<(), ErrorKind> + Send,
+{
+    #[inline]
+    fn transaction(
+        &mut self,
+        now: Duration,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), ErrorKind> {
+        self(now, operations)
+    }
+}
+
+/// Address of an I2C target, including its address mode.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub(crate) enum Address {
+    SevenBit(SevenBitAddress),
+    TenBit(TenBitAddress),
+}
+
+/// I2C bus of a [`Board`].
+///
+/// Transactions are routed to the [`Model`] attached at the target address with [`Board::attach_i2c`]
+/// or [`Board::attach_i2c_ten_bit`]. Transactions to an address without a model fail with
+/// [`NoAcknowledgeSource::Address`].
+///
+/// As on a real bus, 7-bit and 10-bit addresses are distinct, i.e. a model attached at 7-bit
+/// address `0x48` doesn't answer to the 10-bit address `0x048`.
+#[derive(Clone)]
+pub struct Bus {
+    board: Board,
+}
+
+impl Bus {
+    pub(crate) fn new(board: Board) -> Self {
+        Self { board }
+    }
+
+    fn transaction_impl(
+        &mut self,
+        address: Address,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), ErrorKind> {
+        // Called without the board locked, so the model can use it.
+        let now = self.board.now();
+        match self.board.i2c_model(address) {
+            Some(model) => crate::board::lock(&model).transaction(now, operations),
+            None => Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+        }
+    }
+}
+
+impl ErrorType for Bus {
+    type Error = ErrorKind;
+}
+
+impl I2c<SevenBitAddress> for Bus {
+    #[inline]
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.transaction_impl(Address::SevenBit(address), operations)
+    }
+}
+
+impl I2c<TenBitAddress> for Bus {
+    #[inline]
+    fn transaction(
+        &mut self,
+        address: TenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.transaction_impl(Address::TenBit(address), operations)
+    }
+}
@@ -0,0 +1,17 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+#![cfg_attr(target_os = "none", no_std)]
+
+#[cfg(not(target_os = "none"))]
+mod board;
+#[cfg(not(target_os = "none"))]
+pub use board::Board;
+
+#[cfg(not(target_os = "none"))]
+pub mod delay;
+#[cfg(not(target_os = "none"))]
+pub mod digital;
+#[cfg(not(target_os = "none"))]
+pub mod i2c;
+#[cfg(not(target_os = "none"))]
+pub mod spi;
@@ -0,0 +1,74 @@
+//! Simulated SPI devices.
+
+use crate::Board;
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiDevice};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Model of an SPI device.
+///
+/// Implemented for closures with a matching signature, which is often enough for simple devices.
+pub trait Model: Send {
+    /// Handle a transaction, i.e. everything between asserting and deasserting CS.
+    ///
+    /// `now` is the virtual time at the start of the transaction. The model fills the read buffers
+    /// of the operations, and returns an error to simulate a bus fault.
+    ///
+    /// [`Operation::DelayNs`] operations are passed to the model as-is. The virtual clock is advanced
+    /// by their total duration once the model returns.
+    fn transaction(
+        &mut self,
+        now: Duration,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), ErrorKind>;
+}
+
+impl<F> Model for F
+where
+    F: FnMut(Duration, &mut [Operation<'_, u8>]) -> Result<(), ErrorKind> + Send,
+{
+    #[inline]
+    fn transaction(
+        &mut self,
+        now: Duration,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), ErrorKind> {
+        self(now, operations)
+    }
+}
+
+/// SPI device of a [`Board`], connected to the [`Model`] it was created with by [`Board::attach_spi`].
+#[derive(Clone)]
+pub struct Device {
+    board: Board,
+    model: Arc<Mutex<dyn Model>>,
+}
+
+impl Device {
+    pub(crate) fn new(board: Board, model: Arc<Mutex<dyn Model>>) -> Self {
+        Self { board, model }
+    }
+}
+
+impl ErrorType for Device {
+    type Error = ErrorKind;
+}
+
+impl SpiDevice for Device {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        // Called without the board locked, so the model can use it.
+        let now = self.board.now();
+        let res = crate::board::lock(&self.model).transaction(now, operations);
+
+        let delay: u64 = operations
+            .iter()
+            .map(|op| match op {
+                Operation::DelayNs(ns) => u64::from(*ns),
+                _ => 0,
+            })
+            .sum();
+        self.board.advance(Duration::from_nanos(delay));
+
+        res
+    }
+}
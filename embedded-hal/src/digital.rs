@@ -83,6 +83,7 @@ impl<T: ErrorType + ?Sized> ErrorType for &mut T {
 /// ```
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PinState {
     /// Low pin state.
     Low,
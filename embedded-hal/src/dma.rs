@@ -0,0 +1,407 @@
+//! DMA transfer handles.
+//!
+//! Peripherals that move data in the background using DMA hand ownership of the buffer
+//! to the hardware for the duration of the transfer. This module provides a single
+//! ownership-transfer pattern shared by all such APIs, so HAL authors implement one
+//! trait and driver authors learn one handle type regardless of the bus.
+//!
+//! # For HAL authors
+//!
+//! Implement [`Channel`] for the type that represents an in-flight DMA transfer (usually
+//! the DMA channel or stream itself). Methods that start a background transfer take the
+//! buffer by value, program the hardware and return a [`Transfer`] wrapping both the buffer
+//! and the channel:
+//!
+//! ```
+//! use embedded_hal::dma::{self, Channel, ErrorType, Transfer};
+//!
+//! pub struct Dma0;
+//!
+//! impl ErrorType for Dma0 {
+//!     type Error = dma::ErrorKind;
+//! }
+//!
+//! impl Channel for Dma0 {
+//!     fn is_complete(&mut self) -> Result<bool, Self::Error> {
+//!         // Check the transfer-complete flag of the channel.
+//! #       Ok(true)
+//!     }
+//!
+//!     fn abort(&mut self) -> Result<(), Self::Error> {
+//!         // Disable the channel and wait until it no longer accesses memory.
+//! #       Ok(())
+//!     }
+//! }
+//!
+//! pub struct Spi0 {
+//!     dma: Dma0,
+//! }
+//!
+//! impl Spi0 {
+//!     pub fn write_dma(self, buf: &'static [u8]) -> Transfer<&'static [u8], Dma0> {
+//!         // Point the DMA channel at `buf` and start it.
+//!         // SAFETY: `buf` is `'static`, so it stays valid at the same address however
+//!         // the returned transfer is used, even if it is leaked.
+//!         unsafe { Transfer::new(buf, self.dma) }
+//!     }
+//! }
+//! ```
+//!
+//! # For driver authors
+//!
+//! A [`Transfer`] can be [polled](Transfer::poll) while doing other work, or [waited on](Transfer::wait)
+//! until completion. Both ways give the buffer and the channel back once the hardware is done with them.
+//!
+//! ```
+//! # use embedded_hal::dma::{self, Channel, ErrorType, Transfer};
+//! # pub struct Dma0;
+//! # impl ErrorType for Dma0 { type Error = dma::ErrorKind; }
+//! # impl Channel for Dma0 {
+//! #     fn is_complete(&mut self) -> Result<bool, Self::Error> { Ok(true) }
+//! #     fn abort(&mut self) -> Result<(), Self::Error> { Ok(()) }
+//! # }
+//! # pub struct Spi0 { dma: Dma0 }
+//! # impl Spi0 {
+//! #     pub fn write_dma(self, buf: &'static [u8]) -> Transfer<&'static [u8], Dma0> {
+//! #         unsafe { Transfer::new(buf, self.dma) }
+//! #     }
+//! # }
+//! # let spi = Spi0 { dma: Dma0 };
+//! static BUF: [u8; 4] = [1, 2, 3, 4];
+//! let transfer = spi.write_dma(&BUF);
+//!
+//! // Do something else while the transfer is running...
+//!
+//! let (buf, channel) = transfer.wait().map_err(|(e, _, _)| e)?;
+//! # Ok::<(), dma::ErrorKind>(())
+//! ```
+//!
+//! Dropping a [`Transfer`] before it has completed [aborts](Channel::abort) it, and blocks until the
+//! hardware no longer accesses the buffer. Leaking a [`Transfer`] (e.g. with [`core::mem::forget`])
+//! skips this, which is why [`Transfer::new`] is `unsafe`: HALs must only hand buffers to the hardware
+//! that stay valid even then, such as `&'static mut [u8]`.
+//!
+//! # Memory ordering
+//!
+//! The compiler and the CPU don't know the DMA controller accesses the buffer. HALs must issue a
+//! [`fence`](core::sync::atomic::fence) with [`Release`](core::sync::atomic::Ordering::Release)
+//! ordering after the CPU last writes the buffer and before starting the transfer, and one with
+//! [`Acquire`](core::sync::atomic::Ordering::Acquire) ordering after observing completion and before
+//! [`Channel::is_complete`] returns `true` (and before [`Channel::abort`] returns `Ok`). Otherwise
+//! reads of the buffer may be reordered before the hardware has finished writing it.
+
+use core::task::Poll;
+
+#[cfg(feature = "defmt-03")]
+use crate::defmt;
+
+/// DMA error.
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic DMA error kind.
+    ///
+    /// By using this method, DMA errors freely defined by HAL implementations
+    /// can be converted to a set of generic DMA errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// DMA error kind.
+///
+/// This represents a common set of DMA operation errors. HAL implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common DMA errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The DMA controller reported a bus error while accessing memory or the peripheral.
+    Bus,
+    /// A different error occurred. The original error may contain more information.
+    Other,
+}
+
+impl Error for ErrorKind {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl core::error::Error for ErrorKind {}
+
+impl core::fmt::Display for ErrorKind {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bus => write!(f, "The DMA controller reported a bus error"),
+            Self::Other => write!(
+                f,
+                "A different error occurred. The original error may contain more information"
+            ),
+        }
+    }
+}
+
+/// DMA error type trait.
+///
+/// This just defines the error type, to be used by the other DMA traits.
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+impl<T: ErrorType + ?Sized> ErrorType for &mut T {
+    type Error = T::Error;
+}
+
+/// A DMA channel running a background transfer.
+pub trait Channel: ErrorType {
+    /// Returns `true` once the transfer has finished and the hardware no longer accesses
+    /// the buffer.
+    ///
+    /// Errors reported by the hardware during the transfer are returned here. After
+    /// returning an error, the channel must no longer access the buffer.
+    ///
+    /// Before returning `true`, implementations must issue an
+    /// [`Acquire`](core::sync::atomic::Ordering::Acquire) fence, see
+    /// [memory ordering](self#memory-ordering).
+    fn is_complete(&mut self) -> Result<bool, Self::Error>;
+
+    /// Stop the transfer.
+    ///
+    /// When this returns `Ok`, the hardware must no longer access the buffer, and
+    /// [`is_complete`](Channel::is_complete) must return `true`. The same
+    /// [memory ordering](self#memory-ordering) requirements apply.
+    fn abort(&mut self) -> Result<(), Self::Error>;
+}
+
+impl<T: Channel + ?Sized> Channel for &mut T {
+    #[inline]
+    fn is_complete(&mut self) -> Result<bool, Self::Error> {
+        T::is_complete(self)
+    }
+
+    #[inline]
+    fn abort(&mut self) -> Result<(), Self::Error> {
+        T::abort(self)
+    }
+}
+
+/// An in-flight DMA transfer.
+///
+/// Owns the buffer and the channel for as long as the hardware may access the buffer.
+/// See the [module-level documentation](self) for details.
+pub struct Transfer<BUF, CHANNEL: Channel> {
+    inner: Option<(BUF, CHANNEL)>,
+}
+
+impl<BUF, CHANNEL: Channel> Transfer<BUF, CHANNEL> {
+    /// Create a new [`Transfer`].
+    ///
+    /// HALs call this after starting a transfer on `channel` that accesses `buffer`.
+    ///
+    /// # Safety
+    ///
+    /// The memory the hardware accesses through `buffer` must stay valid, and must not move or be
+    /// accessed by anything else, until the transfer has completed or been aborted. This must hold
+    /// even if the returned [`Transfer`] is leaked with [`core::mem::forget`] instead of being
+    /// dropped, so in practice `buffer` must own or exclusively borrow `'static` memory at a stable
+    /// address, e.g. `&'static mut [u8]` or a `Box<[u8]>`. Moving `buffer` itself must not move that
+    /// memory, so arrays held by value are not allowed.
+    #[inline]
+    pub unsafe fn new(buffer: BUF, channel: CHANNEL) -> Self {
+        Self {
+            inner: Some((buffer, channel)),
+        }
+    }
+
+    /// Returns a mutable reference to the channel running the transfer.
+    #[inline]
+    pub fn channel_mut(&mut self) -> &mut CHANNEL {
+        &mut self.parts().1
+    }
+
+    /// Returns `true` if the transfer has finished.
+    #[inline]
+    pub fn is_complete(&mut self) -> Result<bool, CHANNEL::Error> {
+        self.channel_mut().is_complete()
+    }
+
+    /// Check whether the transfer has finished, without blocking.
+    ///
+    /// Returns [`Poll::Pending`] while the transfer is still in progress.
+    #[inline]
+    pub fn poll(&mut self) -> Poll<Result<(), CHANNEL::Error>> {
+        match self.is_complete() {
+            Ok(true) => Poll::Ready(Ok(())),
+            Ok(false) => Poll::Pending,
+            Err(e) => Poll::Ready(Err(e)),
+        }
+    }
+
+    /// Block until the transfer has finished, and give the buffer and the channel back.
+    ///
+    /// The buffer and the channel are also given back on error.
+    #[allow(clippy::type_complexity)]
+    pub fn wait(mut self) -> Result<(BUF, CHANNEL), (CHANNEL::Error, BUF, CHANNEL)> {
+        let res = loop {
+            match self.poll() {
+                Poll::Ready(res) => break res,
+                Poll::Pending => {}
+            }
+        };
+        self.release(res)
+    }
+
+    /// Abort the transfer, and give the buffer and the channel back.
+    ///
+    /// The buffer and the channel are also given back on error.
+    #[allow(clippy::type_complexity)]
+    pub fn abort(mut self) -> Result<(BUF, CHANNEL), (CHANNEL::Error, BUF, CHANNEL)> {
+        let res = self.channel_mut().abort();
+        self.release(res)
+    }
+
+    #[inline]
+    fn parts(&mut self) -> &mut (BUF, CHANNEL) {
+        // `inner` is only taken by `release`, which consumes `self`.
+        self.inner.as_mut().unwrap()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn release(
+        mut self,
+        res: Result<(), CHANNEL::Error>,
+    ) -> Result<(BUF, CHANNEL), (CHANNEL::Error, BUF, CHANNEL)> {
+        let (buf, channel) = self.inner.take().unwrap();
+        match res {
+            Ok(()) => Ok((buf, channel)),
+            Err(e) => Err((e, buf, channel)),
+        }
+    }
+}
+
+impl<BUF, CHANNEL: Channel> Drop for Transfer<BUF, CHANNEL> {
+    fn drop(&mut self) {
+        if let Some((_, channel)) = &mut self.inner {
+            // The buffer must not be freed while the hardware may still write to it.
+            if let Ok(false) = channel.is_complete() {
+                let _ = channel.abort();
+                while let Ok(false) = channel.is_complete() {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    #[derive(Debug)]
+    struct MockChannel<'a> {
+        /// Number of `is_complete` calls returning `false` before the transfer completes.
+        pending_polls: usize,
+        error: Option<ErrorKind>,
+        aborts: &'a Cell<usize>,
+    }
+
+    impl<'a> MockChannel<'a> {
+        fn new(pending_polls: usize, aborts: &'a Cell<usize>) -> Self {
+            Self {
+                pending_polls,
+                error: None,
+                aborts,
+            }
+        }
+    }
+
+    impl ErrorType for MockChannel<'_> {
+        type Error = ErrorKind;
+    }
+
+    impl Channel for MockChannel<'_> {
+        fn is_complete(&mut self) -> Result<bool, Self::Error> {
+            if let Some(e) = self.error {
+                return Err(e);
+            }
+            if self.pending_polls == 0 {
+                return Ok(true);
+            }
+            self.pending_polls -= 1;
+            Ok(false)
+        }
+
+        fn abort(&mut self) -> Result<(), Self::Error> {
+            self.aborts.set(self.aborts.get() + 1);
+            self.pending_polls = 0;
+            Ok(())
+        }
+    }
+
+    static BUF: [u8; 4] = [1, 2, 3, 4];
+
+    fn transfer(channel: MockChannel<'_>) -> Transfer<&'static [u8], MockChannel<'_>> {
+        // SAFETY: `BUF` is `'static`.
+        unsafe { Transfer::new(&BUF, channel) }
+    }
+
+    #[test]
+    fn wait_until_complete() {
+        let aborts = Cell::new(0);
+        let mut t = transfer(MockChannel::new(2, &aborts));
+        assert_eq!(t.poll(), Poll::Pending);
+
+        let (buf, channel) = t.wait().unwrap();
+        assert_eq!(buf, &BUF);
+        assert_eq!(channel.pending_polls, 0);
+        assert_eq!(aborts.get(), 0);
+    }
+
+    #[test]
+    fn wait_returns_parts_on_error() {
+        let aborts = Cell::new(0);
+        let mut channel = MockChannel::new(2, &aborts);
+        channel.error = Some(ErrorKind::Bus);
+
+        let Err((e, buf, channel)) = transfer(channel).wait() else {
+            panic!("transfer should fail");
+        };
+        assert_eq!(e, ErrorKind::Bus);
+        assert_eq!(buf, &BUF);
+        assert_eq!(channel.pending_polls, 2);
+        assert_eq!(aborts.get(), 0);
+    }
+
+    #[test]
+    fn abort_returns_parts() {
+        let aborts = Cell::new(0);
+        let (buf, channel) = transfer(MockChannel::new(2, &aborts)).abort().unwrap();
+        assert_eq!(buf, &BUF);
+        assert_eq!(channel.pending_polls, 0);
+        assert_eq!(aborts.get(), 1);
+    }
+
+    #[test]
+    fn drop_unfinished_aborts() {
+        let aborts = Cell::new(0);
+        drop(transfer(MockChannel::new(2, &aborts)));
+        assert_eq!(aborts.get(), 1);
+    }
+
+    #[test]
+    fn drop_completed_does_not_abort() {
+        let aborts = Cell::new(0);
+        let mut t = transfer(MockChannel::new(0, &aborts));
+        assert_eq!(t.poll(), Poll::Ready(Ok(())));
+        drop(t);
+        assert_eq!(aborts.get(), 0);
+    }
+}
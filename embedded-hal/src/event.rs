@@ -0,0 +1,148 @@
+//! Peripheral event (interrupt) traits.
+//!
+//! Most peripherals can raise an interrupt when something happens: a transfer completes,
+//! data arrives, an error is detected... [`Listen`] lets generic code arm and disarm these
+//! interrupts, and check and clear the corresponding pending flags, without knowing the
+//! vendor-specific register layout.
+//!
+//! HALs implement `Listen<Event>` for the common [`Event`]s their peripherals support,
+//! and may implement `Listen` for their own event types to expose everything else.
+//! The interrupt handler itself remains application or HAL specific.
+//!
+//! ```
+//! use embedded_hal::event::{Event, Listen};
+//!
+//! fn start_transfer<P: Listen>(peripheral: &mut P) -> Result<(), P::Error> {
+//!     peripheral.clear(Event::TransferComplete)?;
+//!     peripheral.listen(Event::TransferComplete)?;
+//!     // Start the transfer, then wait for the interrupt.
+//!     Ok(())
+//! }
+//! ```
+
+#[cfg(feature = "defmt-03")]
+use crate::defmt;
+
+/// Error
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind.
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// Error kind.
+///
+/// This represents a common set of operation errors. HAL implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The peripheral can't signal this event.
+    Unsupported,
+    /// A different error occurred. The original error may contain more information.
+    Other,
+}
+
+impl Error for ErrorKind {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl core::error::Error for ErrorKind {}
+
+impl core::fmt::Display for ErrorKind {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "The peripheral can't signal this event"),
+            Self::Other => write!(
+                f,
+                "A different error occurred. The original error may contain more information"
+            ),
+        }
+    }
+}
+
+/// Error type trait.
+///
+/// This just defines the error type, to be used by the other traits.
+pub trait ErrorType {
+    /// Error type
+    type Error: Error;
+}
+
+impl<T: ErrorType + ?Sized> ErrorType for &mut T {
+    type Error = T::Error;
+}
+
+/// Common peripheral events.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Event {
+    /// A transfer or conversion has completed.
+    TransferComplete,
+    /// Received data is ready to be read.
+    ReceiveReady,
+    /// The peripheral is ready to accept data to transmit.
+    TransmitReady,
+    /// The peripheral detected an error.
+    Error,
+    /// A timeout elapsed, e.g. the bus has been idle for longer than configured.
+    Timeout,
+}
+
+/// Interrupt control for events of type `E`.
+pub trait Listen<E = Event>: ErrorType {
+    /// Enable the interrupt for `event`.
+    fn listen(&mut self, event: E) -> Result<(), Self::Error>;
+
+    /// Disable the interrupt for `event`.
+    ///
+    /// This doesn't clear the pending flag of the event.
+    fn unlisten(&mut self, event: E) -> Result<(), Self::Error>;
+
+    /// Returns `true` if `event` has occurred and hasn't been [cleared](Listen::clear) yet.
+    ///
+    /// This must work whether or not the interrupt for `event` is enabled.
+    fn is_pending(&mut self, event: E) -> Result<bool, Self::Error>;
+
+    /// Clear the pending flag of `event`.
+    fn clear(&mut self, event: E) -> Result<(), Self::Error>;
+}
+
+impl<E, T: Listen<E> + ?Sized> Listen<E> for &mut T {
+    #[inline]
+    fn listen(&mut self, event: E) -> Result<(), Self::Error> {
+        T::listen(self, event)
+    }
+
+    #[inline]
+    fn unlisten(&mut self, event: E) -> Result<(), Self::Error> {
+        T::unlisten(self, event)
+    }
+
+    #[inline]
+    fn is_pending(&mut self, event: E) -> Result<bool, Self::Error> {
+        T::is_pending(self, event)
+    }
+
+    #[inline]
+    fn clear(&mut self, event: E) -> Result<(), Self::Error> {
+        T::clear(self, event)
+    }
+}
@@ -432,3 +432,60 @@ impl<A: AddressMode, T: I2c<A> + ?Sized> I2c<A> for &mut T {
         T::transaction(self, address, operations)
     }
 }
+
+/// Limits and features of an I2C implementation.
+///
+/// This trait is optional. HALs can implement it next to [`I2c`] so generic drivers can adapt to
+/// the hardware at runtime, e.g. by splitting large transfers or choosing a slower protocol mode,
+/// instead of failing on limits they have no other way to discover. Drivers that need it add it
+/// as an extra bound.
+///
+/// All methods have defaults describing an implementation without known limits or extra features,
+/// so HALs only override what applies to them.
+pub trait Capabilities {
+    /// Maximum number of bytes in a single [`Operation`], or `None` if there is no limit.
+    #[inline]
+    fn max_transfer_len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Maximum SCL frequency the bus is able to run at, in Hz, or `None` if unknown.
+    #[inline]
+    fn max_frequency_hz(&self) -> Option<u32> {
+        None
+    }
+
+    /// Returns `true` if [`TenBitAddress`]es are supported.
+    #[inline]
+    fn supports_ten_bit_addresses(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if transfers are done by DMA, i.e. they don't keep the CPU busy.
+    #[inline]
+    fn has_dma(&self) -> bool {
+        false
+    }
+}
+
+impl<T: Capabilities + ?Sized> Capabilities for &mut T {
+    #[inline]
+    fn max_transfer_len(&self) -> Option<usize> {
+        T::max_transfer_len(self)
+    }
+
+    #[inline]
+    fn max_frequency_hz(&self) -> Option<u32> {
+        T::max_frequency_hz(self)
+    }
+
+    #[inline]
+    fn supports_ten_bit_addresses(&self) -> bool {
+        T::supports_ten_bit_addresses(self)
+    }
+
+    #[inline]
+    fn has_dma(&self) -> bool {
+        T::has_dma(self)
+    }
+}
@@ -4,10 +4,14 @@
 
 pub mod delay;
 pub mod digital;
+pub mod dma;
+pub mod event;
 pub mod i2c;
 pub mod pwm;
 pub mod spi;
 
+mod macros;
+
 mod private {
     use crate::i2c::{SevenBitAddress, TenBitAddress};
     pub trait Sealed {}
@@ -0,0 +1,72 @@
+//! Helper macros for HAL implementations.
+
+/// Implement a module's `Error` trait by mapping each error variant to an `ErrorKind`.
+///
+/// The first argument is the module of the trait (`digital`, `dma`, `event`, `i2c`, `pwm` or `spi`).
+/// It's followed by the error type and a list of `pattern => kind` arms, as in a `match` on
+/// `&self`. The module's `ErrorKind` is in scope within the arms.
+///
+/// ```
+/// use embedded_hal::i2c::NoAcknowledgeSource;
+///
+/// #[derive(Debug)]
+/// pub enum I2cError {
+///     AddressNack,
+///     DataNack,
+///     Timeout,
+///     Overrun(u8),
+/// }
+///
+/// embedded_hal::impl_error_kind!(i2c, I2cError {
+///     I2cError::AddressNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+///     I2cError::DataNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+///     I2cError::Overrun(_) => ErrorKind::Overrun,
+///     _ => ErrorKind::Other,
+/// });
+///
+/// use embedded_hal::i2c::{Error, ErrorKind};
+/// assert_eq!(I2cError::Overrun(3).kind(), ErrorKind::Overrun);
+/// assert_eq!(I2cError::Timeout.kind(), ErrorKind::Other);
+/// ```
+#[macro_export]
+macro_rules! impl_error_kind {
+    ($module:ident, $error:ty { $($pattern:pat => $kind:expr),+ $(,)? }) => {
+        impl $crate::$module::Error for $error {
+            #[inline]
+            fn kind(&self) -> $crate::$module::ErrorKind {
+                #[allow(unused_imports)]
+                use $crate::$module::ErrorKind;
+
+                match self {
+                    $($pattern => $kind,)+
+                }
+            }
+        }
+    };
+}
+
+/// Implement a module's `ErrorType` trait for one or more types sharing the same error type.
+///
+/// The first argument is the module of the trait (`digital`, `dma`, `event`, `i2c`, `pwm` or `spi`).
+/// It's followed by the implementing types and the error type. Generic types need their
+/// `ErrorType` implementation to be written by hand.
+///
+/// ```
+/// # #[derive(Debug)]
+/// # pub struct SpiError;
+/// # embedded_hal::impl_error_kind!(spi, SpiError { _ => ErrorKind::Other });
+/// pub struct Spi1;
+/// pub struct Spi2;
+///
+/// embedded_hal::impl_error_type!(spi, Spi1, Spi2 => SpiError);
+/// ```
+#[macro_export]
+macro_rules! impl_error_type {
+    ($module:ident, $($type:ty),+ => $error:ty) => {
+        $(
+            impl $crate::$module::ErrorType for $type {
+                type Error = $error;
+            }
+        )+
+    };
+}
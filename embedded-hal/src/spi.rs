@@ -179,6 +179,7 @@ use crate::defmt;
 /// Clock polarity.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Polarity {
     /// Clock signal low when idle.
     IdleLow,
@@ -189,6 +190,7 @@ pub enum Polarity {
 /// Clock phase.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Phase {
     /// Data in "captured" on the first clock transition.
     CaptureOnFirstTransition,
@@ -199,6 +201,7 @@ pub enum Phase {
 /// SPI mode.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mode {
     /// Clock polarity.
     pub polarity: Polarity,
@@ -506,3 +509,49 @@ impl<T: SpiBus<Word> + ?Sized, Word: Copy + 'static> SpiBus<Word> for &mut T {
         T::flush(self)
     }
 }
+
+/// Limits and features of an SPI implementation.
+///
+/// This trait is optional. HALs can implement it next to [`SpiBus`] or [`SpiDevice`] so generic
+/// drivers can adapt to the hardware at runtime, e.g. by splitting large transfers, instead of
+/// failing on limits they have no other way to discover. Drivers that need it add it as an extra
+/// bound.
+///
+/// All methods have defaults describing an implementation without known limits or extra features,
+/// so HALs only override what applies to them.
+pub trait Capabilities {
+    /// Maximum number of words in a single transfer, or `None` if there is no limit.
+    #[inline]
+    fn max_transfer_len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Maximum SCK frequency the bus is able to run at, in Hz, or `None` if unknown.
+    #[inline]
+    fn max_frequency_hz(&self) -> Option<u32> {
+        None
+    }
+
+    /// Returns `true` if transfers are done by DMA, i.e. they don't keep the CPU busy.
+    #[inline]
+    fn has_dma(&self) -> bool {
+        false
+    }
+}
+
+impl<T: Capabilities + ?Sized> Capabilities for &mut T {
+    #[inline]
+    fn max_transfer_len(&self) -> Option<usize> {
+        T::max_transfer_len(self)
+    }
+
+    #[inline]
+    fn max_frequency_hz(&self) -> Option<u32> {
+        T::max_frequency_hz(self)
+    }
+
+    #[inline]
+    fn has_dma(&self) -> bool {
+        T::has_dma(self)
+    }
+}